@@ -0,0 +1,81 @@
+//! Compares the broad phases of the physics with 500 bodies.
+//! Run with `cargo bench --bench broad_phase`.
+
+use bevy::{prelude::*, time::TimeUpdateStrategy, utils::Instant};
+use bounce_up::{BroadPhase, Motion, PhysicsLayers, PhysicsPlugin, RigidBody, Shape, TimeScale};
+use std::time::Duration;
+
+const BODIES: usize = 500;
+const BLOCKS: usize = 100;
+const STEPS: usize = 360;
+const PHYSICS_TIME_STEP: f32 = 1.0 / 180.0;
+const ARENA: Vec2 = Vec2::new(800.0, 600.0);
+
+fn spawn_bodies(mut commands: Commands) {
+    let rng = fastrand::Rng::with_seed(42);
+    let position = || (Vec2::new(rng.f32(), rng.f32()) - 0.5) * ARENA;
+
+    for _ in 0..BLOCKS {
+        commands.spawn((
+            Transform::from_translation(position().extend(0.0)),
+            RigidBody::new(Shape::Box(Vec2::new(48.0, 16.0)), 0.0, 1.0, 0.0),
+            PhysicsLayers::BOUNDARY,
+        ));
+    }
+
+    for _ in BLOCKS..BODIES {
+        let velocity = (Vec2::new(rng.f32(), rng.f32()) - 0.5) * 1000.0;
+        commands.spawn((
+            Transform::from_translation(position().extend(0.0)),
+            RigidBody::new(Shape::Circle(8.0), 1.0, 1.0, 0.5),
+            Motion {
+                velocity,
+                ..Default::default()
+            },
+            PhysicsLayers::BALL,
+        ));
+    }
+}
+
+/// Steps the physics and returns the elapsed wall time, with the final positions of the bodies.
+fn run(broad_phase: BroadPhase) -> (Duration, Vec<Vec3>) {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .init_resource::<TimeScale>()
+        .add_plugin(PhysicsPlugin)
+        .insert_resource(broad_phase)
+        .add_startup_system(spawn_bodies);
+
+    let mut instant = Instant::now();
+    let mut elapsed = Duration::ZERO;
+    for _ in 0..STEPS {
+        instant += Duration::from_secs_f32(PHYSICS_TIME_STEP);
+        app.insert_resource(TimeUpdateStrategy::ManualInstant(instant));
+
+        let start = Instant::now();
+        app.update();
+        elapsed += start.elapsed();
+    }
+
+    let mut query = app.world.query::<&Transform>();
+    let positions = query
+        .iter(&app.world)
+        .map(|transform| transform.translation)
+        .collect();
+    (elapsed, positions)
+}
+
+fn main() {
+    let (brute_force, expected) = run(BroadPhase::BruteForce);
+    let (sweep_and_prune, positions) = run(BroadPhase::SweepAndPrune);
+
+    assert_eq!(positions, expected, "broad phases disagree");
+
+    println!("{BODIES} bodies, {STEPS} steps");
+    println!("brute force:     {brute_force:?}");
+    println!("sweep and prune: {sweep_and_prune:?}");
+    println!(
+        "speed-up:        {:.1}x",
+        brute_force.as_secs_f64() / sweep_and_prune.as_secs_f64()
+    );
+}
@@ -1,6 +1,6 @@
 use crate::{
     constants::{ARENA_HEIGHT, ARENA_WIDTH, BACKGROUND_SHADER},
-    TimeScale,
+    utils::GameClock,
 };
 use bevy::{
     prelude::*,
@@ -55,12 +55,8 @@ fn setup(
     });
 }
 
-fn update(
-    time: Res<Time>,
-    time_scale: Res<TimeScale>,
-    mut materials: ResMut<Assets<BackgroundMaterial>>,
-) {
-    for (_, mut material) in materials.iter_mut() {
-        material.time += time.delta_seconds() * time_scale.0;
+fn update(clock: Res<GameClock>, mut materials: ResMut<Assets<BackgroundMaterial>>) {
+    for (_, material) in materials.iter_mut() {
+        material.time += clock.scaled_delta_seconds();
     }
 }
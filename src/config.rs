@@ -0,0 +1,366 @@
+use crate::{
+    constants::*,
+    game::{Ball, Difficulty, DifficultyPreset, Enemy, EnemyBase, Player, PlayerAssist},
+};
+use bevy::{
+    asset::{AssetLoader, LoadContext, LoadedAsset},
+    prelude::*,
+    reflect::TypeUuid,
+    utils::BoxedFuture,
+};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::{sync::RwLock, time::Duration};
+
+/// The config read by the [`Default`] impls of the game-play components.
+static CURRENT: RwLock<GameConfig> = RwLock::new(GameConfig::BUILT_IN);
+
+/// Loads the [`GameConfig`] asset at [`GAME_CONFIG`], and applies it again whenever the file changes.
+pub struct ConfigPlugin;
+
+impl Plugin for ConfigPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_asset::<GameConfig>()
+            .init_asset_loader::<GameConfigLoader>()
+            .add_startup_system(load_game_config)
+            .add_system(apply_game_config);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PlayerConfig {
+    pub max_speed: f32,
+    pub sensitivity: f32,
+    pub damp: f32,
+}
+
+impl Default for PlayerConfig {
+    fn default() -> Self {
+        GameConfig::BUILT_IN.player
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AssistConfig {
+    pub range: f32,
+    pub speed: f32,
+    pub vertical_speed_threshold: f32,
+    pub speed_threshold: f32,
+}
+
+impl Default for AssistConfig {
+    fn default() -> Self {
+        GameConfig::BUILT_IN.assist
+    }
+}
+
+/// The enemy of [`Difficulty::Normal`]; the other difficulties have their own speeds and hit range.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EnemyConfig {
+    pub min_speed: f32,
+    pub max_speed: f32,
+    pub normal_speed: f32,
+    pub damp: f32,
+    pub hit_range: Vec2,
+    pub hit_speed_threshold: f32,
+    pub hit_height_threshold: f32,
+}
+
+impl Default for EnemyConfig {
+    fn default() -> Self {
+        GameConfig::BUILT_IN.enemy
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EnemyBaseConfig {
+    /// Health of the [`Difficulty::Normal`] enemy base.
+    pub full_hp: f32,
+}
+
+impl Default for EnemyBaseConfig {
+    fn default() -> Self {
+        GameConfig::BUILT_IN.enemy_base
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BallConfig {
+    pub gravity: f32,
+    pub max_speed: f32,
+    /// Seconds before a lost ball is set back to its origin.
+    pub set_duration: f32,
+    /// Seconds before a lost ball moves again.
+    pub active_duration: f32,
+}
+
+impl Default for BallConfig {
+    fn default() -> Self {
+        GameConfig::BUILT_IN.ball
+    }
+}
+
+/// A [`DifficultyPreset`] as written in the config, where missing fields keep the built-in values.
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct PresetPatch {
+    min_speed: Option<f32>,
+    max_speed: Option<f32>,
+    normal_speed: Option<f32>,
+    hit_range: Option<Vec2>,
+    reaction_latency: Option<f32>,
+    prediction_noise: Option<f32>,
+    enemy_base_hp: Option<f32>,
+}
+
+impl PresetPatch {
+    fn apply(self, preset: DifficultyPreset) -> DifficultyPreset {
+        DifficultyPreset {
+            min_speed: self.min_speed.unwrap_or(preset.min_speed),
+            max_speed: self.max_speed.unwrap_or(preset.max_speed),
+            normal_speed: self.normal_speed.unwrap_or(preset.normal_speed),
+            hit_range: self.hit_range.unwrap_or(preset.hit_range),
+            reaction_latency: self.reaction_latency.unwrap_or(preset.reaction_latency),
+            prediction_noise: self.prediction_noise.unwrap_or(preset.prediction_noise),
+            enemy_base_hp: self.enemy_base_hp.unwrap_or(preset.enemy_base_hp),
+        }
+    }
+}
+
+fn easy_preset<'de, D: Deserializer<'de>>(deserializer: D) -> Result<DifficultyPreset, D::Error> {
+    Ok(PresetPatch::deserialize(deserializer)?.apply(GameConfig::BUILT_IN.easy))
+}
+
+fn hard_preset<'de, D: Deserializer<'de>>(deserializer: D) -> Result<DifficultyPreset, D::Error> {
+    Ok(PresetPatch::deserialize(deserializer)?.apply(GameConfig::BUILT_IN.hard))
+}
+
+fn nightmare_preset<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<DifficultyPreset, D::Error> {
+    Ok(PresetPatch::deserialize(deserializer)?.apply(GameConfig::BUILT_IN.nightmare))
+}
+
+/// Also a resource of the physics, updated whenever the config is applied.
+#[derive(Debug, Clone, Copy, PartialEq, Resource, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PhysicsConfig {
+    /// Bodies hitting each other slower than this do not bounce.
+    pub rest_speed: f32,
+}
+
+impl Default for PhysicsConfig {
+    fn default() -> Self {
+        GameConfig::BUILT_IN.physics
+    }
+}
+
+/// Tuning of the game-play components, loaded from [`GAME_CONFIG`] so that it can change without a rebuild.
+/// Missing fields keep their built-in values from [`crate::constants`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, TypeUuid)]
+#[uuid = "54be4d39-de6f-4226-90f9-d2abdb7d5181"]
+#[serde(default)]
+pub struct GameConfig {
+    pub player: PlayerConfig,
+    pub assist: AssistConfig,
+    pub enemy: EnemyConfig,
+    pub enemy_base: EnemyBaseConfig,
+    /// The enemies of the other difficulties, with their bases.
+    #[serde(deserialize_with = "easy_preset")]
+    pub easy: DifficultyPreset,
+    #[serde(deserialize_with = "hard_preset")]
+    pub hard: DifficultyPreset,
+    #[serde(deserialize_with = "nightmare_preset")]
+    pub nightmare: DifficultyPreset,
+    pub ball: BallConfig,
+    pub physics: PhysicsConfig,
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        Self::BUILT_IN
+    }
+}
+
+impl GameConfig {
+    pub const BUILT_IN: Self = Self {
+        player: PlayerConfig {
+            max_speed: PLAYER_MAX_SPEED,
+            sensitivity: PLAYER_SENSITIVITY,
+            damp: PLAYER_DAMP,
+        },
+        assist: AssistConfig {
+            range: PLAYER_ASSIST_RANGE,
+            speed: PLAYER_ASSIST_SPEED,
+            vertical_speed_threshold: PLAYER_ASSIST_VERTICAL_SPEED_THRESHOLD,
+            speed_threshold: PLAYER_ASSIST_SPEED_THRESHOLD,
+        },
+        enemy: EnemyConfig {
+            min_speed: ENEMY_MIN_SPEED,
+            max_speed: ENEMY_MAX_SPEED,
+            normal_speed: ENEMY_NORMAL_SPEED,
+            damp: ENEMY_DAMP,
+            hit_range: Vec2::new(ENEMY_HIT_RANGE_HORIZONTAL, ENEMY_HIT_RANGE_VERTICAL),
+            hit_speed_threshold: ENEMY_HIT_SPEED_THRESHOLD,
+            hit_height_threshold: 0.125 * ARENA_HEIGHT,
+        },
+        enemy_base: EnemyBaseConfig {
+            full_hp: ENEMY_BASE_FULL_HP,
+        },
+        easy: DifficultyPreset {
+            min_speed: EASY_ENEMY_MIN_SPEED,
+            max_speed: EASY_ENEMY_MAX_SPEED,
+            normal_speed: EASY_ENEMY_NORMAL_SPEED,
+            hit_range: Vec2::new(
+                EASY_ENEMY_HIT_RANGE_HORIZONTAL,
+                EASY_ENEMY_HIT_RANGE_VERTICAL,
+            ),
+            reaction_latency: EASY_ENEMY_REACTION_LATENCY,
+            prediction_noise: EASY_ENEMY_PREDICTION_NOISE,
+            enemy_base_hp: EASY_ENEMY_BASE_FULL_HP,
+        },
+        hard: DifficultyPreset {
+            min_speed: HARD_ENEMY_MIN_SPEED,
+            max_speed: HARD_ENEMY_MAX_SPEED,
+            normal_speed: HARD_ENEMY_NORMAL_SPEED,
+            hit_range: Vec2::new(
+                HARD_ENEMY_HIT_RANGE_HORIZONTAL,
+                HARD_ENEMY_HIT_RANGE_VERTICAL,
+            ),
+            reaction_latency: HARD_ENEMY_REACTION_LATENCY,
+            prediction_noise: HARD_ENEMY_PREDICTION_NOISE,
+            enemy_base_hp: HARD_ENEMY_BASE_FULL_HP,
+        },
+        nightmare: DifficultyPreset {
+            min_speed: NIGHTMARE_ENEMY_MIN_SPEED,
+            max_speed: NIGHTMARE_ENEMY_MAX_SPEED,
+            normal_speed: NIGHTMARE_ENEMY_NORMAL_SPEED,
+            hit_range: Vec2::new(
+                NIGHTMARE_ENEMY_HIT_RANGE_HORIZONTAL,
+                NIGHTMARE_ENEMY_HIT_RANGE_VERTICAL,
+            ),
+            reaction_latency: NIGHTMARE_ENEMY_REACTION_LATENCY,
+            prediction_noise: NIGHTMARE_ENEMY_PREDICTION_NOISE,
+            enemy_base_hp: NIGHTMARE_ENEMY_BASE_FULL_HP,
+        },
+        ball: BallConfig {
+            gravity: BALL_GRAVITY,
+            max_speed: BALL_MAX_SPEED,
+            set_duration: BALL_SET_DURATION,
+            active_duration: BALL_ACTIVE_DURATION,
+        },
+        physics: PhysicsConfig {
+            rest_speed: PHYSICS_REST_SPEED,
+        },
+    };
+
+    /// The latest loaded config, or the built-in one before any is loaded.
+    pub fn current() -> Self {
+        *CURRENT.read().unwrap()
+    }
+
+    pub fn set_current(config: Self) {
+        *CURRENT.write().unwrap() = config;
+    }
+
+    /// Reads the config file directly, for the [`crate::headless`] runs that have no asset server.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn read_file() -> Option<Self> {
+        let path = bevy::asset::FileAssetIo::get_base_path()
+            .join("assets")
+            .join(GAME_CONFIG);
+        let text = std::fs::read_to_string(&path).ok()?;
+        match ron::from_str(&text) {
+            Ok(config) => Some(config),
+            Err(err) => {
+                warn!("Failed to parse {}: {err}", path.display());
+                None
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct GameConfigLoader;
+
+impl AssetLoader for GameConfigLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move {
+            let config: GameConfig = ron::de::from_bytes(bytes)?;
+            load_context.set_default_asset(LoadedAsset::new(config));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["config.ron"]
+    }
+}
+
+#[derive(Resource)]
+struct GameConfigHandle(Handle<GameConfig>);
+
+fn load_game_config(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(GameConfigHandle(asset_server.load(GAME_CONFIG)));
+}
+
+/// Makes the loaded config current, and retunes the live entities in place when the file changes.
+#[allow(clippy::too_many_arguments)]
+fn apply_game_config(
+    mut events: EventReader<AssetEvent<GameConfig>>,
+    handle: Res<GameConfigHandle>,
+    configs: Res<Assets<GameConfig>>,
+    difficulty: Res<Difficulty>,
+    mut players: Query<(&mut Player, Option<&mut PlayerAssist>)>,
+    mut enemies: Query<&mut Enemy>,
+    mut bases: Query<&mut EnemyBase>,
+    mut balls: Query<&mut Ball>,
+    mut physics: ResMut<PhysicsConfig>,
+) {
+    let changed = events.iter().any(|event| match event {
+        AssetEvent::Created { handle: changed } | AssetEvent::Modified { handle: changed } => {
+            *changed == handle.0
+        }
+        AssetEvent::Removed { .. } => false,
+    });
+    let Some(config) = configs.get(&handle.0).filter(|_| changed) else {
+        return;
+    };
+
+    info!("Applying {GAME_CONFIG}");
+    GameConfig::set_current(*config);
+    *physics = config.physics;
+
+    for (mut player, assist) in players.iter_mut() {
+        *player = Player::default();
+        if let Some(mut assist) = assist {
+            *assist = PlayerAssist::default();
+        }
+    }
+
+    let preset = difficulty.preset();
+    for mut enemy in enemies.iter_mut() {
+        enemy.retune(&preset);
+    }
+    for mut base in bases.iter_mut() {
+        base.full_hp = preset.enemy_base_hp;
+        base.hp = base.hp.min(base.full_hp);
+    }
+
+    for mut ball in balls.iter_mut() {
+        ball.gravity = config.ball.gravity;
+        ball.max_speed = config.ball.max_speed;
+        ball.set_timer
+            .set_duration(Duration::from_secs_f32(config.ball.set_duration));
+        ball.active_timer
+            .set_duration(Duration::from_secs_f32(config.ball.active_duration));
+    }
+}
@@ -4,30 +4,14 @@ pub const ARENA_WIDTH: f32 = 480.0;
 pub const ARENA_HEIGHT: f32 = 640.0;
 
 pub const DEATH_EFFECT_LAYER: RenderLayers = RenderLayers::layer(1);
+/// Layer of no entity, so that the UI camera draws the UI only.
+pub const UI_CAMERA_LAYER: RenderLayers = RenderLayers::layer(2);
 
 pub const BACKGROUND_SHADER: &str = "shaders/background.wgsl";
-
-pub const FONT_FIRA_MONO: &str = "fonts/FiraMono-Medium.ttf";
-pub const FONT_FIRA_SANS: &str = "fonts/FiraSans-Bold.ttf";
-pub const FONT_ARCADE: &str = "fonts/Arcade.ttf";
-pub const FONT_KARMATIC: &str = "fonts/Karmatic.ttf";
-pub const FONT_BOXY: &str = "fonts/Boxy-Bold.ttf";
-pub const FONT_ALAGARD: &str = "fonts/Alagard.ttf";
-pub const FONT_INVASION: &str = "fonts/Invasion2000.ttf";
-
-pub const PLAYER_SPRITE: &str = "sprites/player.png";
-pub const ENEMY_SPRITE: &str = "sprites/enemy.png";
-pub const BALL_SPRITE: &str = "sprites/ball.png";
-pub const HINT_SPRITE: &str = "sprites/hint.png";
-pub const DEATH_SPRITE: &str = "sprites/death.png";
-pub const HIT_SPRITE: &str = "sprites/hit.png";
-
-pub const GAME_ICON: &str = "sprites/icons/icon.png";
-pub const RIGHT_ICON: &str = "sprites/icons/right.png";
-pub const HELP_ICON: &str = "sprites/icons/help.png";
-pub const RETICLE_ICON: &str = "sprites/icons/reticle.png";
-pub const WRENCH_ICON: &str = "sprites/icons/wrench.png";
-pub const EXIT_ICON: &str = "sprites/icons/exit.png";
+pub const GAME_CONFIG: &str = "game.config.ron";
+pub const ASSET_MANIFEST: &str = "assets.manifest.ron";
+/// Font of the loading screen, which shows before the [`ASSET_MANIFEST`] is loaded.
+pub const LOADING_FONT: &str = "fonts/FiraMono-Medium.ttf";
 
 pub const PADDLE_COLOR: Color = Color::rgba(0.608, 0.678, 0.718, 0.392);
 pub const SEPARATE_COLOR: Color = Color::rgba(0.5, 0.5, 0.5, 0.2);
@@ -35,6 +19,9 @@ pub const BOUNDARY_COLOR: Color = Color::NONE;
 pub const HEALTH_BAR_COLOR: Color = Color::rgb(0.608, 0.678, 0.718);
 pub const HEALTH_BAR_TRACKER_COLOR: Color = Color::rgb(0.851, 0.341, 0.388);
 pub const HINT_COLOR: Color = Color::rgba(1.0, 1.0, 1.0, 0.2);
+pub const PAUSE_BACKGROUND_COLOR: Color = Color::rgba(0.0, 0.0, 0.0, 0.6);
+pub const LOADING_BAR_COLOR: Color = Color::rgb(0.608, 0.678, 0.718);
+pub const LOADING_BAR_BACKGROUND_COLOR: Color = Color::rgba(0.5, 0.5, 0.5, 0.2);
 
 pub const BUTTON_NORMAL_COLOR: Color = Color::NONE;
 pub const BUTTON_HOVERED_COLOR: Color = Color::WHITE;
@@ -50,18 +37,6 @@ pub const BUTTON_TEXT_PRESSED_COLOR: Color = Color::BLACK;
 
 pub const FLIP_TEXT_COLORS: [Color; 2] = [Color::WHITE, Color::GOLD];
 
-pub const MISS_AUDIO: &str = "audios/miss.flac";
-pub const EXPLOSION_AUDIO: &str = "audios/explosion.flac";
-pub const LOSE_AUDIO: &str = "audios/lose.flac";
-pub const HIT_AUDIO: &str = "audios/hit.ogg";
-pub const IMPACT_AUDIOS: [&str; 2] = ["audios/impacts/impact-1.ogg", "audios/impacts/impact-2.ogg"];
-
-pub const BUTTON_HOVER_AUDIO: &str = "audios/button/hover.ogg";
-pub const BUTTON_CLICK_AUDIO: &str = "audios/button/click.ogg";
-
-pub const MENU_MUSIC: &str = "musics/E2M2 Myrgharok - Halls of Wandering Spirits.ogg";
-pub const GAME_MUSIC: &str = "musics/E3M8 Myrgharok - Mother of All Doom.ogg";
-
 pub const MENU_MUSIC_BPM: f32 = 102.0;
 
 pub const PREDICT_SIZE: usize = 100;
@@ -70,6 +45,7 @@ pub const AI_TIME_STEP: f32 = 0.1;
 
 pub const PHYSICS_REST_SPEED: f32 = 100.0;
 pub const PHYSICS_TIME_STEP: f32 = 1.0 / 180.0;
+pub const PHYSICS_MAX_TIME_OF_IMPACTS: usize = 4;
 
 pub const PADDLE_WIDTH: f32 = 96.0;
 pub const PADDLE_HEIGHT: f32 = 16.0;
@@ -84,6 +60,11 @@ pub const PLAYER_ASSIST_SPEED: f32 = 1000.0;
 pub const PLAYER_ASSIST_VERTICAL_SPEED_THRESHOLD: f32 = -200.0;
 pub const PLAYER_ASSIST_SPEED_THRESHOLD: f32 = 1000.0;
 
+pub const CONTROL_SPEED: f32 = 4000.0;
+pub const CONTROL_RAMP_TIME: f32 = 0.25;
+pub const CONTROL_RAMP_START: f32 = 0.2;
+pub const CONTROL_STICK_THRESHOLD: f32 = 0.5;
+
 pub const ENEMY_MIN_SPEED: f32 = 500.0;
 pub const ENEMY_MAX_SPEED: f32 = 2000.0;
 pub const ENEMY_NORMAL_SPEED: f32 = 1250.0;
@@ -93,6 +74,33 @@ pub const ENEMY_HIT_RANGE_VERTICAL: f32 = 144.0;
 pub const ENEMY_HIT_RANGE_HORIZONTAL: f32 = 144.0;
 pub const ENEMY_HIT_SPEED_THRESHOLD: f32 = -0.0;
 
+pub const EASY_ENEMY_MIN_SPEED: f32 = 400.0;
+pub const EASY_ENEMY_MAX_SPEED: f32 = 1500.0;
+pub const EASY_ENEMY_NORMAL_SPEED: f32 = 900.0;
+pub const EASY_ENEMY_HIT_RANGE_VERTICAL: f32 = 112.0;
+pub const EASY_ENEMY_HIT_RANGE_HORIZONTAL: f32 = 112.0;
+pub const EASY_ENEMY_REACTION_LATENCY: f32 = 0.2;
+pub const EASY_ENEMY_PREDICTION_NOISE: f32 = 80.0;
+pub const EASY_ENEMY_BASE_FULL_HP: f32 = 30000.0;
+
+pub const HARD_ENEMY_MIN_SPEED: f32 = 600.0;
+pub const HARD_ENEMY_MAX_SPEED: f32 = 2400.0;
+pub const HARD_ENEMY_NORMAL_SPEED: f32 = 1500.0;
+pub const HARD_ENEMY_HIT_RANGE_VERTICAL: f32 = 160.0;
+pub const HARD_ENEMY_HIT_RANGE_HORIZONTAL: f32 = 160.0;
+pub const HARD_ENEMY_REACTION_LATENCY: f32 = 0.0;
+pub const HARD_ENEMY_PREDICTION_NOISE: f32 = 0.0;
+pub const HARD_ENEMY_BASE_FULL_HP: f32 = 50000.0;
+
+pub const NIGHTMARE_ENEMY_MIN_SPEED: f32 = 800.0;
+pub const NIGHTMARE_ENEMY_MAX_SPEED: f32 = 3000.0;
+pub const NIGHTMARE_ENEMY_NORMAL_SPEED: f32 = 1800.0;
+pub const NIGHTMARE_ENEMY_HIT_RANGE_VERTICAL: f32 = 176.0;
+pub const NIGHTMARE_ENEMY_HIT_RANGE_HORIZONTAL: f32 = 176.0;
+pub const NIGHTMARE_ENEMY_REACTION_LATENCY: f32 = 0.0;
+pub const NIGHTMARE_ENEMY_PREDICTION_NOISE: f32 = 0.0;
+pub const NIGHTMARE_ENEMY_BASE_FULL_HP: f32 = 60000.0;
+
 pub const SLIT_BLOCK_WIDTH: f32 = 96.0;
 pub const SLIT_BLOCK_HEIGHT: f32 = 16.0;
 pub const SLIT_POSITION_VERTICAL: f32 = 200.0;
@@ -101,8 +109,29 @@ pub const PLAYER_BASE_BALL_COUNT: i32 = 3;
 pub const ENEMY_BASE_FULL_HP: f32 = 40000.0;
 pub const MAX_DAMAGE: f32 = 2000.0;
 
+pub const SCORE_HIT_POINTS: i32 = 100;
+pub const SCORE_BALL_POINTS: i32 = 1000;
+pub const SCORE_MISS_PENALTY: i32 = 200;
+pub const SCORE_TIME_BONUS: f32 = 3000.0;
+pub const SCORE_TIME_PENALTY: f32 = 10.0;
+pub const RECORDS_COUNT: usize = 10;
+pub const RECORD_INITIALS_LENGTH: usize = 3;
+
 pub const BALL_GHOSTS_COUNT: usize = 16;
+pub const BALL_GRAVITY: f32 = -1000.0;
 pub const BALL_MAX_SPEED: f32 = 3000.0;
+pub const BALL_SET_DURATION: f32 = 1.0;
+pub const BALL_ACTIVE_DURATION: f32 = 2.0;
+pub const BALL_MAX_SPIN: f32 = 40.0;
+pub const BALL_SPIN_DAMP: f32 = 0.8;
+pub const BALL_SPIN_TRANSFER: f32 = 0.1;
+pub const BALL_SPIN_GRIP: f32 = 0.25;
+pub const BALL_MAGNUS: f32 = 0.02;
+pub const BALL_GHOSTS_SPIN_BEND: f32 = 0.02;
+
+pub const MULTI_BALL_MILESTONE: i32 = 5;
+pub const MULTI_BALL_MAX_COUNT: usize = 3;
+pub const MULTI_BALL_SPACING: f32 = 64.0;
 
 pub const MIN_BOUNCE_AUDIO_SPEED: f32 = 500.0;
 pub const MAX_BOUNCE_AUDIO_SPEED: f32 = 2500.0;
@@ -0,0 +1,170 @@
+use crate::{
+    constants::{ARENA_HEIGHT, ARENA_WIDTH},
+    settings::DisplaySettings,
+    MainCamera,
+};
+use bevy::{prelude::*, render::camera::Viewport, ui::UiSystem};
+
+/// Scales the arena to the window by whole pixels and letterboxes the rest,
+/// lays out the root UI nodes over the arena at the same scale, and applies the [`DisplaySettings`].
+pub struct DisplayPlugin;
+
+impl Plugin for DisplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ArenaScale>()
+            .add_system_to_stage(CoreStage::PreUpdate, apply_display_settings)
+            .add_system_to_stage(CoreStage::PreUpdate, fit_arena)
+            .add_system_to_stage(CoreStage::PostUpdate, fit_camera)
+            .add_system_to_stage(CoreStage::PostUpdate, fit_ui_roots.before(UiSystem::Flex));
+    }
+}
+
+/// Where the arena is drawn in the window, in physical pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Resource)]
+pub struct ArenaScale {
+    /// Physical pixels per arena unit; a whole number unless the window is smaller than the arena.
+    pub scale: f32,
+    pub position: UVec2,
+    pub size: UVec2,
+    pub window: UVec2,
+}
+
+impl Default for ArenaScale {
+    fn default() -> Self {
+        Self::new(UVec2::new(ARENA_WIDTH as u32, ARENA_HEIGHT as u32))
+    }
+}
+
+impl ArenaScale {
+    /// The largest whole scale of the arena that fits into a window of the given physical size, centered.
+    pub fn new(window: UVec2) -> Self {
+        let fit = (window.x as f32 / ARENA_WIDTH).min(window.y as f32 / ARENA_HEIGHT);
+        let scale = if fit >= 1.0 { fit.floor() } else { fit };
+        let size = (Vec2::new(ARENA_WIDTH, ARENA_HEIGHT) * scale)
+            .round()
+            .as_uvec2()
+            .min(window)
+            .max(UVec2::ONE);
+        Self {
+            scale,
+            position: (window - size) / 2,
+            size,
+            window,
+        }
+    }
+}
+
+fn apply_display_settings(display: Res<DisplaySettings>, mut windows: ResMut<Windows>) {
+    if let Some(window) = windows.get_primary_mut() {
+        if window.mode() != display.window_mode() {
+            window.set_mode(display.window_mode());
+        }
+    }
+}
+
+fn fit_arena(
+    windows: Res<Windows>,
+    mut arena_scale: ResMut<ArenaScale>,
+    mut ui_scale: ResMut<UiScale>,
+) {
+    let Some(window) = windows.get_primary() else {
+        return;
+    };
+    // minimized
+    if window.physical_width() == 0 || window.physical_height() == 0 {
+        return;
+    }
+
+    let fitted = ArenaScale::new(UVec2::new(
+        window.physical_width(),
+        window.physical_height(),
+    ));
+    if *arena_scale != fitted {
+        *arena_scale = fitted;
+    }
+
+    let scale = fitted.scale as f64 / window.scale_factor();
+    if ui_scale.scale != scale {
+        ui_scale.scale = scale;
+    }
+}
+
+/// Renders the arena into its letterboxed viewport; the projection of the camera fits the arena into it.
+fn fit_camera(arena_scale: Res<ArenaScale>, mut cameras: Query<&mut Camera, With<MainCamera>>) {
+    if !arena_scale.is_changed() {
+        return;
+    }
+
+    for mut camera in cameras.iter_mut() {
+        camera.viewport = Some(Viewport {
+            physical_position: arena_scale.position,
+            physical_size: arena_scale.size,
+            ..Default::default()
+        });
+    }
+}
+
+/// The style a root UI node was spawned with, relative to the window.
+#[derive(Component)]
+struct ArenaNode(Style);
+
+impl ArenaNode {
+    /// The style relative to the arena instead: the [`UiScale`] makes a pixel of UI an arena unit,
+    /// so percentages become arena units, and the node is placed over the arena.
+    fn fit(&self, arena_scale: &ArenaScale) -> Style {
+        let arena = Vec2::new(ARENA_WIDTH, ARENA_HEIGHT);
+        let near = arena_scale.position.as_vec2() / arena_scale.scale;
+        let far = arena_scale.window.as_vec2() / arena_scale.scale - near - arena;
+        let to_arena = |val: Val, full: f32| match val {
+            Val::Percent(percent) => Val::Px(0.01 * percent * full),
+            val => val,
+        };
+        let with_offset = |val: Val, full: f32, offset: f32| match to_arena(val, full) {
+            Val::Px(px) => Val::Px(offset + px),
+            val => val,
+        };
+
+        let style = &self.0;
+        let mut position = UiRect {
+            left: with_offset(style.position.left, arena.x, near.x),
+            right: with_offset(style.position.right, arena.x, far.x),
+            top: with_offset(style.position.top, arena.y, near.y),
+            bottom: with_offset(style.position.bottom, arena.y, far.y),
+        };
+        if !matches!(position.left, Val::Px(_)) && !matches!(position.right, Val::Px(_)) {
+            position.left = Val::Px(near.x);
+        }
+        if !matches!(position.top, Val::Px(_)) && !matches!(position.bottom, Val::Px(_)) {
+            position.top = Val::Px(near.y);
+        }
+
+        Style {
+            position_type: PositionType::Absolute,
+            position,
+            size: Size::new(
+                to_arena(style.size.width, arena.x),
+                to_arena(style.size.height, arena.y),
+            ),
+            ..style.clone()
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn fit_ui_roots(
+    mut commands: Commands,
+    arena_scale: Res<ArenaScale>,
+    mut query: Query<(Entity, &mut Style, Option<&ArenaNode>), (With<Node>, Without<Parent>)>,
+) {
+    for (entity, mut style, arena_node) in query.iter_mut() {
+        match arena_node {
+            Some(arena_node) if arena_scale.is_changed() => *style = arena_node.fit(&arena_scale),
+            Some(_) => {}
+            None => {
+                let arena_node = ArenaNode(style.clone());
+                *style = arena_node.fit(&arena_scale);
+                commands.entity(entity).insert(arena_node);
+            }
+        }
+    }
+}
@@ -1,14 +1,19 @@
 use crate::{
     constants::{ARENA_HEIGHT, ARENA_WIDTH, DEATH_EFFECT_LAYER, HIT_EFFECT_TIME_STEP},
-    MainCamera, TimeScale,
+    display::ArenaScale,
+    utils::GameClock,
+    MainCamera,
 };
 use bevy::{
     core_pipeline::clear_color::ClearColorConfig,
     prelude::*,
     reflect::TypeUuid,
     render::{
-        camera::RenderTarget, mesh::InnerMeshVertexBufferLayout, render_asset::RenderAssets,
-        render_resource::*, texture::BevyDefault,
+        camera::{RenderTarget, ScalingMode},
+        mesh::InnerMeshVertexBufferLayout,
+        render_asset::RenderAssets,
+        render_resource::*,
+        texture::BevyDefault,
     },
     sprite::{
         ColorMaterialFlags, ColorMaterialUniform, Material2d, Material2dKey, Material2dPlugin,
@@ -16,7 +21,6 @@ use bevy::{
     },
     utils::{FixedState, Hashed},
 };
-use std::time::Duration;
 
 pub struct EffectsPlugin;
 
@@ -27,12 +31,14 @@ impl Plugin for EffectsPlugin {
             .insert_resource(CameraShakeTimer(Timer::from_seconds(0.02, TimerMode::Once)))
             .add_event::<CameraShakeEvent>()
             .add_startup_system(setup)
+            .add_system(resize_death_effect_texture)
             .add_system(death_effect_system)
             .add_system(hit_effect_system)
             .add_system(camera_shake_system);
     }
 }
 
+/// Render target of the death effects, as large as the arena on screen.
 #[derive(Resource)]
 pub struct DeathEffectTexture(Handle<Image>);
 
@@ -163,6 +169,13 @@ fn setup(
             camera_2d: Camera2d {
                 clear_color: ClearColorConfig::Custom(Color::NONE),
             },
+            projection: OrthographicProjection {
+                scaling_mode: ScalingMode::Auto {
+                    min_width: ARENA_WIDTH,
+                    min_height: ARENA_HEIGHT,
+                },
+                ..Default::default()
+            },
             ..Default::default()
         },
         UiCameraConfig { show_ui: false },
@@ -179,24 +192,37 @@ fn setup(
     });
 }
 
+fn resize_death_effect_texture(
+    arena_scale: Res<ArenaScale>,
+    texture: Res<DeathEffectTexture>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    if !arena_scale.is_changed() {
+        return;
+    }
+
+    if let Some(image) = images.get_mut(&texture.0) {
+        image.resize(Extent3d {
+            width: arena_scale.size.x,
+            height: arena_scale.size.y,
+            depth_or_array_layers: 1,
+        });
+    }
+}
+
 fn death_effect_system(
     mut commands: Commands,
-    time: Res<Time>,
-    time_scale: Res<TimeScale>,
+    clock: Res<GameClock>,
     mut query: Query<(Entity, &mut Transform, &mut DeathEffect)>,
 ) {
     for (entity, mut transform, mut effect) in query.iter_mut() {
-        if effect
-            .timer
-            .tick(Duration::from_secs_f32(time.delta_seconds() * time_scale.0))
-            .just_finished()
-        {
+        if effect.timer.tick(clock.scaled_delta()).just_finished() {
             commands.entity(entity).despawn();
             continue;
         }
 
-        effect.speed += effect.acceleration * time.delta_seconds() * time_scale.0;
-        transform.scale += effect.speed * time.delta_seconds() * time_scale.0;
+        effect.speed += effect.acceleration * clock.scaled_delta_seconds();
+        transform.scale += effect.speed * clock.scaled_delta_seconds();
     }
 }
 
@@ -215,8 +241,7 @@ impl Default for HitEffect {
 
 fn hit_effect_system(
     mut commands: Commands,
-    time: Res<Time>,
-    time_scale: Res<TimeScale>,
+    clock: Res<GameClock>,
     texture_atlases: Res<Assets<TextureAtlas>>,
     mut query: Query<(
         Entity,
@@ -226,11 +251,7 @@ fn hit_effect_system(
     )>,
 ) {
     for (entity, mut effect, mut sprite, texture_atlas_handle) in query.iter_mut() {
-        if effect
-            .timer
-            .tick(Duration::from_secs_f32(time.delta_seconds() * time_scale.0))
-            .just_finished()
-        {
+        if effect.timer.tick(clock.scaled_delta()).just_finished() {
             if let Some(texture_atlas) = texture_atlases.get(texture_atlas_handle) {
                 if sprite.index + 1 < texture_atlas.len() {
                     sprite.index += 1;
@@ -251,7 +272,7 @@ pub struct CameraShakeEvent {
 
 fn camera_shake_system(
     mut events: EventReader<CameraShakeEvent>,
-    time: Res<Time>,
+    clock: Res<GameClock>,
     mut timer: ResMut<CameraShakeTimer>,
     mut cameras: Query<(&mut Transform, &Camera), With<MainCamera>>,
     mut camera_position: Local<Option<Vec3>>,
@@ -262,7 +283,7 @@ fn camera_shake_system(
                 *camera_position = Some(transform.translation);
             }
 
-            if timer.tick(time.delta()).just_finished() {
+            if timer.tick(clock.delta()).just_finished() {
                 transform.translation = camera_position.unwrap_or_default();
             }
 
@@ -0,0 +1,133 @@
+use super::{
+    ball::{Ball, Point, Trajectory},
+    base::PlayerBase,
+    brain::{BallSnapshot, BaseSnapshot, Brain, BrainKind, EnemySnapshot},
+    difficulty::Difficulty,
+    enemy::{threat, Enemy},
+    physics::Motion,
+    player::{Player, PlayerTwo},
+};
+use crate::{constants::*, utils::GameClock, GameRng};
+use bevy::prelude::*;
+
+/// Drives the player's paddle with an [`super::brain::EnemyBrain`] instead of the mouse.
+/// The brain sees the arena mirrored vertically, as if it played on the enemy's side.
+#[derive(Component)]
+pub struct Autopilot {
+    pub enemy: Enemy,
+    pub brain: Brain,
+    /// Decided velocity, which [`super::player::assist_player`] starts the paddle's controller from.
+    pub velocity: Vec2,
+}
+
+impl Autopilot {
+    pub fn new(brain: BrainKind, difficulty: Difficulty) -> Self {
+        Self {
+            enemy: Enemy::new(&difficulty.preset()),
+            brain: brain.brain(),
+            velocity: Vec2::ZERO,
+        }
+    }
+}
+
+/// Spawns the player's paddle with an [`Autopilot`] when a brain is set.
+#[derive(Debug, Default, Clone, Copy, Resource)]
+pub struct SelfPlay {
+    pub brain: Option<BrainKind>,
+    pub difficulty: Difficulty,
+}
+
+fn mirror(vector: Vec2) -> Vec2 {
+    Vec2::new(vector.x, -vector.y)
+}
+
+fn mirror_transform(transform: &Transform) -> Transform {
+    let mut transform = *transform;
+    transform.translation.y = -transform.translation.y;
+    transform
+}
+
+fn mirror_motion(motion: &Motion) -> Motion {
+    let mut translation = motion.translation;
+    translation.y = -translation.y;
+    Motion {
+        velocity: mirror(motion.velocity),
+        translation,
+    }
+}
+
+fn mirror_trajectory(trajectory: &Trajectory) -> Trajectory {
+    Trajectory {
+        start_time: trajectory.start_time,
+        points: trajectory
+            .points
+            .iter()
+            .map(|point| Point {
+                position: mirror(point.position),
+                velocity: mirror(point.velocity),
+                ..*point
+            })
+            .collect(),
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn control_autopilot(
+    clock: Res<GameClock>,
+    mut rng: ResMut<GameRng>,
+    mut query: Query<(&Transform, &Motion, &mut Autopilot), (With<Player>, Without<Ball>)>,
+    ball_query: Query<(&Transform, &Motion, &Trajectory), With<Ball>>,
+    base_query: Query<(Entity, &PlayerBase), Without<PlayerTwo>>,
+) {
+    if query.is_empty() {
+        return;
+    }
+
+    let elapsed_seconds = clock.elapsed_seconds();
+
+    let balls: Vec<_> = ball_query
+        .iter()
+        .map(|(transform, motion, trajectory)| {
+            (
+                mirror_transform(transform),
+                mirror_motion(motion),
+                mirror_trajectory(trajectory),
+            )
+        })
+        .collect();
+    let target = balls.iter().min_by(|(_, a, x), (_, b, y)| {
+        threat(a, x, elapsed_seconds).total_cmp(&threat(b, y, elapsed_seconds))
+    });
+    let base = base_query
+        .get_single()
+        .ok()
+        .map(|(entity, base)| BaseSnapshot {
+            entity,
+            health: base.ball_count as f32,
+            full_health: PLAYER_BASE_BALL_COUNT as f32,
+        });
+    let rng = rng.gameplay();
+
+    for (transform, motion, mut autopilot) in query.iter_mut() {
+        let transform = mirror_transform(transform);
+        let motion = mirror_motion(motion);
+        let autopilot = &mut *autopilot;
+
+        let snapshot = EnemySnapshot {
+            enemy: &autopilot.enemy,
+            transform: &transform,
+            motion: &motion,
+            ball: target.map(|(transform, motion, trajectory)| BallSnapshot {
+                transform,
+                motion,
+                trajectory,
+            }),
+            base,
+            elapsed_seconds,
+            rng,
+        };
+        let velocity = autopilot.brain.0.think(&snapshot);
+
+        autopilot.velocity = mirror(autopilot.enemy.react(elapsed_seconds, velocity));
+    }
+}
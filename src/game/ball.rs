@@ -1,38 +1,52 @@
-use super::physics::{Motion, RigidBody};
-use crate::{constants::*, TimeScale};
+use super::physics::{CollisionEvent, Contact, Motion, PhysicsLayers, RigidBody, Sensor};
+use crate::{
+    config::{GameConfig, PhysicsConfig},
+    constants::*,
+    utils::{collide, Collider, Damp, GameClock},
+};
 use bevy::prelude::*;
 use std::f32::consts::FRAC_PI_2;
 
 #[derive(Clone, Component)]
 pub struct Ball {
     pub gravity: f32,
+    pub max_speed: f32,
+    /// Angular velocity in radians per second, counter-clockwise.
+    pub spin: f32,
+    /// Where the ball is set before it becomes movable.
+    pub origin: Vec2,
     pub set_timer: Timer,
     pub active_timer: Timer,
 }
 
 impl Default for Ball {
     fn default() -> Self {
+        let config = GameConfig::current().ball;
         Self {
-            gravity: -1000.0,
-            set_timer: Timer::from_seconds(1.0, TimerMode::Once),
-            active_timer: Timer::from_seconds(2.0, TimerMode::Once),
+            gravity: config.gravity,
+            max_speed: config.max_speed,
+            spin: 0.0,
+            origin: Vec2::ZERO,
+            set_timer: Timer::from_seconds(config.set_duration, TimerMode::Once),
+            active_timer: Timer::from_seconds(config.active_duration, TimerMode::Once),
         }
     }
 }
 
-/// For an unset ball without [`Motion`], moves it to origin and makes it movable after some time.
+/// For an unset ball without [`Motion`], moves it to its origin and makes it movable after some time.
 pub fn activate_ball(
     mut commands: Commands,
-    time: Res<Time>,
+    clock: Res<GameClock>,
     mut query: Query<(Entity, &mut Ball, &mut Transform), Without<Motion>>,
 ) {
     for (entity, mut ball, mut transform) in query.iter_mut() {
-        if ball.set_timer.tick(time.delta()).just_finished() {
-            transform.translation = Vec3::ZERO;
+        if ball.set_timer.tick(clock.delta()).just_finished() {
+            transform.translation = ball.origin.extend(0.0);
         }
 
-        if ball.active_timer.tick(time.delta()).just_finished() {
+        if ball.active_timer.tick(clock.delta()).just_finished() {
             commands.entity(entity).insert(Motion::default());
+            ball.spin = 0.0;
 
             // reset the ball timers
             ball.set_timer.reset();
@@ -42,11 +56,12 @@ pub fn activate_ball(
 }
 
 /// Implements motion blur using a bunch of transparent ghost sprites.
+/// The trail bends with the spin.
 pub fn update_ball(
-    ball_query: Query<(&Children, Option<&Motion>), With<Ball>>,
+    ball_query: Query<(&Ball, &Children, Option<&Motion>)>,
     mut child_query: Query<&mut Transform, Without<Ball>>,
 ) {
-    for (children, motion) in ball_query.iter() {
+    for (ball, children, motion) in ball_query.iter() {
         for (index, child) in children.iter().enumerate() {
             let extent = 2.0 * BALL_SIZE;
             let count = (BALL_GHOSTS_COUNT) as f32;
@@ -56,8 +71,9 @@ pub fn update_ball(
 
             let mut transform = child_query.get_mut(*child).unwrap();
             if let Some(motion) = motion {
-                transform.translation =
-                    (extent * offset * motion.velocity / BALL_MAX_SPEED).extend(0.0);
+                let along = extent * offset * motion.velocity / ball.max_speed;
+                let bend = BALL_GHOSTS_SPIN_BEND * ball.spin * offset * along.perp();
+                transform.translation = (along + bend).extend(0.0);
             } else {
                 transform.translation = Vec3::ZERO;
             }
@@ -65,17 +81,59 @@ pub fn update_ball(
     }
 }
 
-pub fn move_ball(
-    time: Res<Time>,
-    time_scale: Res<TimeScale>,
-    mut query: Query<(&Ball, &mut Motion)>,
-) {
-    for (ball, mut motion) in query.iter_mut() {
-        motion.velocity.y += ball.gravity * time.delta_seconds() * time_scale.0;
+pub fn move_ball(clock: Res<GameClock>, mut query: Query<(&mut Ball, &mut Motion)>) {
+    let delta_seconds = clock.scaled_delta_seconds();
+    for (mut ball, mut motion) in query.iter_mut() {
+        motion.velocity.y += ball.gravity * delta_seconds;
+        let curve = magnus(ball.spin, motion.velocity);
+        motion.velocity += curve * delta_seconds;
+        ball.spin = ball.spin.damp(0.0, BALL_SPIN_DAMP, delta_seconds);
 
         let speed = motion.velocity.length();
-        if speed > BALL_MAX_SPEED {
-            motion.velocity = motion.velocity.normalize() * BALL_MAX_SPEED;
+        if speed > ball.max_speed {
+            motion.velocity = motion.velocity.normalize() * ball.max_speed;
+        }
+    }
+}
+
+/// Acceleration of a ball moving with `spin` at `velocity`, curving its path (Magnus effect).
+pub fn magnus(spin: f32, velocity: Vec2) -> Vec2 {
+    BALL_MAGNUS * spin * velocity.perp()
+}
+
+/// Exchanges spin and velocity when a ball of `radius` gets `delta_velocity` from a contact along `normal`.
+/// The tangential part of the impulse spins the ball up, while the old spin grips the surface.
+/// Returns the new spin and the extra velocity from the grip.
+pub fn spin_contact(spin: f32, radius: f32, normal: Vec2, delta_velocity: Vec2) -> (f32, Vec2) {
+    let grip = BALL_SPIN_GRIP * spin * radius * normal.perp();
+    let spin = spin * (1.0 - 2.0 * BALL_SPIN_GRIP)
+        - 2.0 * BALL_SPIN_TRANSFER * normal.perp_dot(delta_velocity) / radius;
+    (spin.clamp(-BALL_MAX_SPIN, BALL_MAX_SPIN), grip)
+}
+
+/// Applies [`spin_contact`] to balls that collided.
+pub fn spin_ball(
+    mut events: EventReader<CollisionEvent>,
+    mut query: Query<(&mut Ball, &RigidBody, &mut Motion)>,
+) {
+    for event in events.iter() {
+        for (index, entity) in event.entities.into_iter().enumerate() {
+            let delta_velocity = event.delta_velocities[index];
+            if delta_velocity == Vec2::ZERO {
+                continue;
+            }
+            let Ok((mut ball, rigid_body, mut motion)) = query.get_mut(entity) else {
+                continue;
+            };
+
+            let normal = match index {
+                0 => event.hit.normal(),
+                _ => -event.hit.normal(),
+            };
+            let radius = rigid_body.shape.size().x / 2.0;
+            let (spin, grip) = spin_contact(ball.spin, radius, normal, delta_velocity);
+            ball.spin = spin;
+            motion.velocity += grip;
         }
     }
 }
@@ -85,6 +143,8 @@ pub struct Point {
     pub position: Vec2,
     pub velocity: Vec2,
     pub time: f32,
+    /// The body the ball is predicted to bounce off during the step leading to this point.
+    pub bounce: Option<Entity>,
 }
 
 #[derive(Component)]
@@ -102,41 +162,94 @@ impl Default for Trajectory {
     }
 }
 
+/// Predicts the ball trajectory by stepping it against the bodies that do not move,
+/// through the same collision and impulse code as the physics.
+#[allow(clippy::type_complexity)]
 pub fn predict_ball(
-    time: Res<Time>,
-    mut query: Query<(&Ball, &RigidBody, &Motion, &mut Trajectory)>,
+    clock: Res<GameClock>,
+    physics: Res<PhysicsConfig>,
+    mut query: Query<(&Ball, &RigidBody, &PhysicsLayers, &Motion, &mut Trajectory)>,
+    colliders: Query<
+        (Entity, &RigidBody, &Transform, &PhysicsLayers),
+        (Without<Motion>, Without<Ball>, Without<Sensor>),
+    >,
 ) {
-    for (ball, rigid_body, motion, mut trajectory) in query.iter_mut() {
-        let start_time = time.elapsed_seconds();
-        let boundary = (Vec2::new(ARENA_WIDTH, ARENA_HEIGHT) + rigid_body.size) / 2.0;
+    for (ball, rigid_body, layers, motion, mut trajectory) in query.iter_mut() {
+        let start_time = clock.elapsed_seconds();
+
+        // snapshot the static bodies this ball collides with
+        let colliders = colliders
+            .iter()
+            .filter(|(_, _, _, other_layers)| other_layers.collision.intersects(&layers.collision))
+            .map(|(entity, other_rigid_body, transform, other_layers)| {
+                let position = transform.translation.truncate();
+                let collider = Collider {
+                    previous_position: position,
+                    position,
+                    shape: other_rigid_body.shape,
+                };
+                let contact = Contact::new(
+                    rigid_body,
+                    layers,
+                    other_rigid_body,
+                    other_layers,
+                    physics.rest_speed,
+                );
+                (entity, collider, contact)
+            })
+            .collect::<Vec<_>>();
 
         let mut position = motion.translation.truncate();
         let mut velocity = motion.velocity;
+        let mut spin = ball.spin;
         let mut time = 0.0;
+        let radius = rigid_body.shape.size().x / 2.0;
 
         if let Some(point) = trajectory.points.first_mut() {
             *point = Point {
                 position,
                 velocity,
                 time,
+                bounce: None,
             };
         }
 
         trajectory.start_time = start_time;
         for point in trajectory.points.iter_mut().skip(1) {
             velocity.y += ball.gravity * PREDICT_TIME_STEP;
+            velocity += magnus(spin, velocity) * PREDICT_TIME_STEP;
+            spin = spin.damp(0.0, BALL_SPIN_DAMP, PREDICT_TIME_STEP);
+            let previous = position;
             position += velocity * PREDICT_TIME_STEP;
 
-            if position.x.abs() > boundary.x {
-                velocity.x *= -rigid_body.bounciness;
-                velocity.y *= rigid_body.friction;
-                position.x = position.x.clamp(-boundary.x + 0.01, boundary.x - 0.01);
-            }
+            let mut bounce = None;
+            for (entity, collider, contact) in &colliders {
+                let ball_collider = Collider {
+                    previous_position: previous,
+                    position,
+                    shape: rigid_body.shape,
+                };
+                let Some(hit) = collide(&ball_collider, collider) else {
+                    continue;
+                };
 
-            if position.y > boundary.y {
-                velocity.y *= -rigid_body.bounciness;
-                velocity.x *= rigid_body.friction;
-                position.y = position.y.clamp(-boundary.y + 0.01, boundary.y - 0.01);
+                let normal = hit.normal();
+                if let Some(impulse) = contact.resolve(rigid_body, -velocity, normal) {
+                    let (new_spin, grip) =
+                        spin_contact(spin, radius, normal, impulse.delta_velocity);
+                    spin = new_spin;
+                    velocity += impulse.delta_velocity + grip;
+                    position = impulse
+                        .correct(
+                            &hit,
+                            previous.extend(0.0),
+                            position.extend(0.0),
+                            normal,
+                            PREDICT_TIME_STEP,
+                        )
+                        .truncate();
+                    bounce = Some(*entity);
+                }
             }
 
             time += PREDICT_TIME_STEP;
@@ -145,6 +258,7 @@ pub fn predict_ball(
                 position,
                 velocity,
                 time,
+                bounce,
             };
         }
     }
@@ -1,4 +1,9 @@
-use crate::{constants::*, utils::Damp};
+use super::player::PlayerTwo;
+use crate::{
+    config::GameConfig,
+    constants::*,
+    utils::{Damp, GameClock},
+};
 use bevy::prelude::*;
 
 #[derive(Component)]
@@ -23,7 +28,7 @@ pub struct EnemyBase {
 impl Default for EnemyBase {
     fn default() -> Self {
         Self {
-            full_hp: ENEMY_BASE_FULL_HP,
+            full_hp: GameConfig::current().enemy_base.full_hp,
             hp: 0.0,
         }
     }
@@ -32,13 +37,29 @@ impl Default for EnemyBase {
 #[derive(Default, Component)]
 pub struct BallCounter;
 
+/// Shows the ball count of the [`PlayerBase`] of player two in versus.
+#[derive(Default, Component)]
+pub struct PlayerTwoBallCounter;
+
+#[allow(clippy::type_complexity)]
 pub fn count_ball(
-    base_query: Query<&PlayerBase>,
+    base_query: Query<(&PlayerBase, Option<&PlayerTwo>)>,
     mut counter_query: Query<&mut Text, With<BallCounter>>,
+    mut player_two_counter_query: Query<
+        &mut Text,
+        (With<PlayerTwoBallCounter>, Without<BallCounter>),
+    >,
 ) {
-    if let Ok(base) = base_query.get_single() {
-        for mut counter in counter_query.iter_mut() {
-            counter.sections[1].value = base.ball_count.to_string();
+    for (base, player_two) in base_query.iter() {
+        let value = base.ball_count.to_string();
+        if player_two.is_some() {
+            for mut counter in player_two_counter_query.iter_mut() {
+                counter.sections[1].value = value.clone();
+            }
+        } else {
+            for mut counter in counter_query.iter_mut() {
+                counter.sections[1].value = value.clone();
+            }
         }
     }
 }
@@ -72,7 +93,7 @@ pub fn health_bar(base_query: Query<&EnemyBase>, mut query: Query<&mut Style, Wi
 }
 
 pub fn health_bar_tracker(
-    time: Res<Time>,
+    clock: Res<GameClock>,
     base_query: Query<&EnemyBase>,
     mut query: Query<(&mut Style, &mut HealthBarTracker)>,
 ) {
@@ -83,7 +104,7 @@ pub fn health_bar_tracker(
                 (tracker.percent + tracker.bias).damp(
                     percent_hp,
                     tracker.damp,
-                    time.delta_seconds(),
+                    clock.delta_seconds(),
                 ) - tracker.bias,
             );
             health_bar.size.width = Val::Percent(tracker.percent - percent_hp);
@@ -100,16 +121,26 @@ pub struct Heal {
 impl Default for Heal {
     fn default() -> Self {
         Self {
-            amount_per_second: ENEMY_BASE_FULL_HP,
+            amount_per_second: GameConfig::current().enemy_base.full_hp,
             timer: Timer::from_seconds(1.0, TimerMode::Once),
         }
     }
 }
 
+impl Heal {
+    /// Heals a base of `full_hp` completely within the default duration.
+    pub fn full(full_hp: f32) -> Self {
+        Self {
+            amount_per_second: full_hp,
+            ..Default::default()
+        }
+    }
+}
+
 pub struct HealEvent(pub Heal);
 
 pub fn heal_enemy_base(
-    time: Res<Time>,
+    clock: Res<GameClock>,
     mut heal_events: EventReader<HealEvent>,
     mut query: Query<&mut EnemyBase>,
     mut heal: Local<Option<Heal>>,
@@ -120,14 +151,14 @@ pub fn heal_enemy_base(
         }
 
         if let Some(heal) = heal.as_ref() {
-            let amount = heal.amount_per_second * time.delta_seconds();
+            let amount = heal.amount_per_second * clock.delta_seconds();
             base.hp += amount;
             base.hp = base.hp.min(base.full_hp);
         }
 
         if heal
             .as_mut()
-            .map_or(false, |heal| heal.timer.tick(time.delta()).finished())
+            .is_some_and(|heal| heal.timer.tick(clock.delta()).finished())
             || base.hp >= base.full_hp
         {
             *heal = None;
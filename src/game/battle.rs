@@ -8,7 +8,6 @@ impl Plugin for BattlePlugin {
             SystemSet::on_enter(AppState::Battle)
                 .with_system(enter_battle)
                 .with_system(make_arena)
-                .with_system(make_ui)
                 .with_system(make_player)
                 .with_system(make_enemy)
                 .with_system(make_ball),
@@ -16,9 +15,10 @@ impl Plugin for BattlePlugin {
         .add_system_set(
             SystemSet::on_update(AppState::Battle)
                 // logical game-play systems
-                .with_system(escape_system)
+                .with_system(pause_system)
                 .with_system(reset_ball)
                 .with_system(remove_ball)
+                .with_system(multi_ball)
                 .with_system(player_hit)
                 .with_system(player_miss)
                 .with_system(game_over_system),
@@ -29,40 +29,33 @@ impl Plugin for BattlePlugin {
     }
 }
 
-#[allow(clippy::too_many_arguments)]
 fn enter_battle(
-    asset_server: Res<AssetServer>,
-    audio: Res<Audio>,
-    volume: Res<AudioVolume>,
-    mut music_track: ResMut<MusicTrack>,
-    time: Res<Time>,
+    clock: Res<GameClock>,
+    mut rng: ResMut<GameRng>,
+    mut steps: ResMut<FixedSteps>,
     mut time_scale: ResMut<TimeScale>,
     mut score: ResMut<Score>,
     mut heal_events: EventWriter<HealEvent>,
+    difficulty: Res<Difficulty>,
 ) {
     // clear score state
-    score.timestamp = time.elapsed_seconds();
+    score.timestamp = clock.scaled_elapsed_seconds();
     score.hits = 0;
     score.miss = 0;
+    score.ball_count = PLAYER_BASE_BALL_COUNT;
 
-    time_scale.reset();
-
-    heal_events.send(HealEvent(Heal::default()));
+    rng.start_run();
+    steps.reset(&clock);
 
-    if music_track.0 != GAME_MUSIC {
-        audio.stop();
-        audio.set_volume(volume.music.into());
-        audio.set_playback_rate(1.2);
-        audio.play(asset_server.load(GAME_MUSIC)).looped();
+    time_scale.reset();
 
-        music_track.0 = GAME_MUSIC;
-    }
+    heal_events.send(HealEvent(Heal::full(difficulty.preset().enemy_base_hp)));
 }
 
 /// Deals with [`GameOverEvent`].
 /// Changes the [`AppState`] after [`GAME_OVER_STATE_CHANGE_DURATION`].
 fn game_over_system(
-    time: Res<Time>,
+    clock: Res<GameClock>,
     mut app_state: ResMut<State<AppState>>,
     mut game_over_events: EventReader<GameOverEvent>,
     mut game_over: Local<GameOver>,
@@ -71,7 +64,7 @@ fn game_over_system(
         // it's time to switch state
         if game_over
             .state_change_timer
-            .tick(time.delta())
+            .tick(clock.delta())
             .just_finished()
         {
             *game_over = GameOver::default();
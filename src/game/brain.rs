@@ -0,0 +1,320 @@
+use super::{
+    ball::{Point, Trajectory},
+    enemy::Enemy,
+    physics::Motion,
+};
+use crate::constants::*;
+use bevy::prelude::*;
+
+/// Decides where an [`Enemy`] moves, see [`Brain`].
+pub trait EnemyBrain: Send + Sync {
+    /// Returns the velocity for the enemy's [`super::enemy::Controller`].
+    fn think(&mut self, snapshot: &EnemySnapshot) -> Vec2;
+}
+
+/// Read-only view of the game that an [`EnemyBrain`] decides from.
+pub struct EnemySnapshot<'a> {
+    pub enemy: &'a Enemy,
+    pub transform: &'a Transform,
+    pub motion: &'a Motion,
+    /// The ball threatening the enemy's base the most, if any is in play.
+    pub ball: Option<BallSnapshot<'a>>,
+    pub base: Option<BaseSnapshot>,
+    /// Comparable with [`Trajectory::start_time`].
+    pub elapsed_seconds: f32,
+    /// Game-play randomness, for [`Enemy::prediction_noise`].
+    pub rng: &'a fastrand::Rng,
+}
+
+pub struct BallSnapshot<'a> {
+    pub transform: &'a Transform,
+    pub motion: &'a Motion,
+    pub trajectory: &'a Trajectory,
+}
+
+/// The base the brain defends.
+#[derive(Debug, Clone, Copy)]
+pub struct BaseSnapshot {
+    pub entity: Entity,
+    /// HP of the enemy's base, or the balls left in the player's base.
+    pub health: f32,
+    pub full_health: f32,
+}
+
+impl<'a> EnemySnapshot<'a> {
+    pub fn position(&self) -> Vec2 {
+        self.transform.translation.truncate()
+    }
+
+    /// Trajectory points of the ball until it reaches the enemy's base, where the ball is lost.
+    pub fn reachable_points(&self) -> impl Iterator<Item = &'a Point> + '_ {
+        let base = self.base.map(|base| base.entity);
+        self.ball
+            .iter()
+            .flat_map(|ball| ball.trajectory.points.iter())
+            .take_while(move |point| base.is_none() || point.bounce != base)
+    }
+
+    /// Seconds from now until the trajectory point is reached.
+    pub fn time_until(&self, point: &Point) -> f32 {
+        let start_time = self
+            .ball
+            .as_ref()
+            .map_or(self.elapsed_seconds, |ball| ball.trajectory.start_time);
+        point.time - (self.elapsed_seconds - start_time)
+    }
+
+    /// Whether the ball is close below the enemy and coming up, so that it can be struck right now.
+    pub fn can_strike(&self) -> bool {
+        let Some(ball) = &self.ball else {
+            return false;
+        };
+
+        let enemy = self.enemy;
+        let position = self.position();
+        let direction = (ball.transform.translation - self.transform.translation).truncate();
+        direction.x.abs() < enemy.hit_range.x
+            && direction.y > -enemy.hit_range.y
+            && direction.y < -0.0
+            && ball.motion.velocity.y > enemy.hit_speed_threshold
+            && position.y > enemy.hit_height_threshold
+    }
+
+    /// Adds the prediction error of the enemy to a target that is `time` seconds ahead.
+    fn noisy(&self, target: Vec2, time: f32) -> Vec2 {
+        if self.enemy.prediction_noise > 0.0 {
+            let error = Vec2::new(self.rng.f32(), self.rng.f32()) * 2.0 - 1.0;
+            target + self.enemy.prediction_noise * time * error
+        } else {
+            target
+        }
+    }
+}
+
+/// Selects the [`EnemyBrain`] of an enemy entity.
+#[derive(Component)]
+pub struct Brain(pub Box<dyn EnemyBrain>);
+
+impl Brain {
+    pub fn new(brain: impl EnemyBrain + 'static) -> Self {
+        Self(Box::new(brain))
+    }
+}
+
+impl Default for Brain {
+    fn default() -> Self {
+        Self::new(HeuristicBrain)
+    }
+}
+
+/// The built-in brains; the resource picks the brain of spawned enemies.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Resource)]
+pub enum BrainKind {
+    #[default]
+    Heuristic,
+    Tracker,
+    Smasher,
+}
+
+impl BrainKind {
+    pub const ALL: [Self; 3] = [Self::Heuristic, Self::Tracker, Self::Smasher];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            BrainKind::Heuristic => "Heuristic",
+            BrainKind::Tracker => "Tracker",
+            BrainKind::Smasher => "Smasher",
+        }
+    }
+
+    pub fn brain(&self) -> Brain {
+        match self {
+            BrainKind::Heuristic => Brain::new(HeuristicBrain),
+            BrainKind::Tracker => Brain::new(TrackerBrain),
+            BrainKind::Smasher => Brain::new(SmasherBrain),
+        }
+    }
+}
+
+/// Meets the ball at the reachable trajectory point nearest to it and to the top,
+/// and strikes at maximum speed once the ball is close.
+#[derive(Default)]
+pub struct HeuristicBrain;
+
+impl EnemyBrain for HeuristicBrain {
+    fn think(&mut self, snapshot: &EnemySnapshot) -> Vec2 {
+        let Some(ball) = &snapshot.ball else {
+            return Vec2::ZERO;
+        };
+
+        let enemy = snapshot.enemy;
+        let transform = snapshot.transform;
+        let motion = ball.motion;
+        let position = snapshot.position();
+
+        if snapshot.can_strike() {
+            // very close to the ball, reacts in maximum speed
+            let direction = (ball.transform.translation - transform.translation).truncate();
+            return enemy.max_speed * direction.normalize();
+        }
+
+        // find the most suitable trajectory point
+        if let Some(candidate) = snapshot
+            .reachable_points()
+            .filter(|point| point.position.y > 0.0)
+            .filter(|point| point.position.y < ARENA_HEIGHT * 0.375)
+            .filter(|point| point.velocity.y > 0.0)
+            .filter(|point| {
+                // filter reachable points
+                let time = snapshot.time_until(point);
+                let distance = (point.position - position).length();
+                time > distance / enemy.normal_speed
+            })
+            .min_by(|a, b| {
+                let cost = |target: Vec2| {
+                    (target - position).length_squared() + (ARENA_HEIGHT / 2.0 - target.y).powi(2)
+                };
+                cost(a.position)
+                    .partial_cmp(&cost(b.position))
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+        {
+            // find candidate, move to it at proper speed
+            let time = snapshot.time_until(candidate);
+            let target = snapshot.noisy(candidate.position, time);
+
+            let direction = target - position;
+            let distance = direction.length();
+
+            let mut speed = (distance / time + 1.0).clamp(enemy.min_speed, enemy.normal_speed);
+
+            if distance < ENEMY_BRAKE_DISTANCE {
+                speed *= distance / ENEMY_BRAKE_DISTANCE;
+            }
+            speed * direction.normalize()
+        } else {
+            // not found, choose the average trajectory points as the candidate.
+            let collection: Vec<_> = ball
+                .trajectory
+                .points
+                .iter()
+                .map(|point| point.position)
+                .filter(|position| position.y > -ARENA_HEIGHT / 2.0)
+                .map(|position| {
+                    let mut contribution = position;
+                    if position.y < 0.0 {
+                        contribution.y = -position.y;
+                    }
+                    if motion.translation.y > transform.translation.y {
+                        contribution.x = -contribution.x;
+                    }
+                    contribution
+                })
+                .collect();
+
+            let mut candidate: Vec2 = collection.iter().sum();
+            candidate /= collection.len() as f32;
+            candidate.y = candidate
+                .y
+                .clamp(0.125 * ARENA_HEIGHT, 0.375 * ARENA_HEIGHT);
+
+            let direction = candidate - position;
+            let distance = direction.length();
+            let speed = if distance < ENEMY_BRAKE_DISTANCE {
+                if motion.translation.y < 0.0 {
+                    0.0
+                } else {
+                    enemy.normal_speed * distance / ENEMY_BRAKE_DISTANCE
+                }
+            } else {
+                enemy.normal_speed
+            };
+
+            speed * direction.normalize()
+        }
+    }
+}
+
+/// Follows the ball horizontally without any prediction, and strikes once it is close.
+#[derive(Default)]
+pub struct TrackerBrain;
+
+impl EnemyBrain for TrackerBrain {
+    fn think(&mut self, snapshot: &EnemySnapshot) -> Vec2 {
+        let Some(ball) = &snapshot.ball else {
+            return Vec2::ZERO;
+        };
+
+        let enemy = snapshot.enemy;
+        let position = snapshot.position();
+        let ball_position = ball.transform.translation.truncate();
+
+        if snapshot.can_strike() {
+            return enemy.max_speed * (ball_position - position).normalize();
+        }
+
+        let target = Vec2::new(ball_position.x, 0.25 * ARENA_HEIGHT);
+        let direction = target - position;
+        let distance = direction.length();
+
+        let mut speed = enemy.normal_speed;
+        if distance < ENEMY_BRAKE_DISTANCE {
+            speed *= distance / ENEMY_BRAKE_DISTANCE;
+        }
+        speed * direction.normalize_or_zero()
+    }
+}
+
+/// Waits above the highest point it can meet the ball at, then smashes the ball down
+/// at maximum speed to deal as much damage as possible to the [`super::base::PlayerBase`].
+#[derive(Default)]
+pub struct SmasherBrain;
+
+impl EnemyBrain for SmasherBrain {
+    fn think(&mut self, snapshot: &EnemySnapshot) -> Vec2 {
+        let Some(ball) = &snapshot.ball else {
+            return Vec2::ZERO;
+        };
+
+        let enemy = snapshot.enemy;
+        let position = snapshot.position();
+        let ball_position = ball.transform.translation.truncate();
+
+        if snapshot.can_strike() {
+            // charge through the ball, bent downwards to the player's base
+            let direction = (ball_position - position).normalize_or_zero() + Vec2::NEG_Y;
+            return enemy.max_speed * direction.normalize_or_zero();
+        }
+
+        let candidate = snapshot
+            .reachable_points()
+            .filter(|point| point.position.y > 0.0)
+            .filter(|point| point.position.y < ARENA_HEIGHT * 0.375)
+            .filter(|point| point.velocity.y > 0.0)
+            .filter(|point| {
+                let time = snapshot.time_until(point);
+                let distance = (point.position - position).length();
+                time > distance / enemy.max_speed
+            })
+            .max_by(|a, b| a.position.y.total_cmp(&b.position.y));
+
+        // wind up above the candidate for a run-up, or at the top if there is none
+        let target = match candidate {
+            Some(candidate) => {
+                let time = snapshot.time_until(candidate);
+                let target = snapshot.noisy(candidate.position, time);
+                Vec2::new(target.x, target.y + 0.5 * enemy.hit_range.y)
+            }
+            None => Vec2::new(ball_position.x, 0.375 * ARENA_HEIGHT),
+        };
+
+        let direction = target - position;
+        let distance = direction.length();
+
+        let mut speed = enemy.normal_speed;
+        if distance < ENEMY_BRAKE_DISTANCE {
+            speed *= distance / ENEMY_BRAKE_DISTANCE;
+        }
+        speed * direction.normalize_or_zero()
+    }
+}
@@ -0,0 +1,73 @@
+use crate::config::GameConfig;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Named strength presets of the enemy, chosen in the settings.
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Resource,
+    Serialize,
+    Deserialize,
+)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+    Nightmare,
+}
+
+/// Enemy parameters bundled by a [`Difficulty`].
+/// The presets other than Normal are read from the [`GameConfig`] as they are.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DifficultyPreset {
+    pub min_speed: f32,
+    pub max_speed: f32,
+    pub normal_speed: f32,
+    pub hit_range: Vec2,
+    /// Seconds before the enemy acts on a decision, on top of [`crate::constants::AI_TIME_STEP`].
+    pub reaction_latency: f32,
+    /// Error of the predicted target position, in pixels per second looked ahead.
+    pub prediction_noise: f32,
+    pub enemy_base_hp: f32,
+}
+
+impl Difficulty {
+    pub const ALL: [Self; 4] = [Self::Easy, Self::Normal, Self::Hard, Self::Nightmare];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Normal => "Normal",
+            Difficulty::Hard => "Hard",
+            Difficulty::Nightmare => "Nightmare",
+        }
+    }
+
+    pub fn preset(&self) -> DifficultyPreset {
+        let config = GameConfig::current();
+        match self {
+            Difficulty::Easy => config.easy,
+            // follows the enemy of the game config
+            Difficulty::Normal => DifficultyPreset {
+                min_speed: config.enemy.min_speed,
+                max_speed: config.enemy.max_speed,
+                normal_speed: config.enemy.normal_speed,
+                hit_range: config.enemy.hit_range,
+                reaction_latency: 0.0,
+                prediction_noise: 0.0,
+                enemy_base_hp: config.enemy_base.full_hp,
+            },
+            Difficulty::Hard => config.hard,
+            Difficulty::Nightmare => config.nightmare,
+        }
+    }
+}
@@ -1,9 +1,18 @@
 use super::{
     ball::{Ball, Trajectory},
+    base::EnemyBase,
+    brain::{BallSnapshot, BaseSnapshot, Brain, EnemySnapshot},
+    difficulty::DifficultyPreset,
     physics::Motion,
 };
-use crate::{constants::*, utils::Damp, TimeScale};
+use crate::{
+    config::GameConfig,
+    constants::*,
+    utils::{Damp, GameClock},
+    GameRng,
+};
 use bevy::prelude::*;
+use std::collections::VecDeque;
 
 #[derive(Component)]
 pub struct Enemy {
@@ -15,18 +24,67 @@ pub struct Enemy {
     pub hit_range: Vec2,
     pub hit_speed_threshold: f32,
     pub hit_height_threshold: f32,
+
+    pub reaction_latency: f32,
+    pub prediction_noise: f32,
+    /// Decided velocities and when they were decided, waiting for the reaction latency.
+    decisions: VecDeque<(f32, Vec2)>,
 }
 
 impl Default for Enemy {
     fn default() -> Self {
+        let config = GameConfig::current().enemy;
         Self {
-            min_speed: ENEMY_MIN_SPEED,
-            max_speed: ENEMY_MAX_SPEED,
-            normal_speed: ENEMY_NORMAL_SPEED,
-            damp: ENEMY_DAMP,
-            hit_range: Vec2::new(ENEMY_HIT_RANGE_HORIZONTAL, ENEMY_HIT_RANGE_VERTICAL),
-            hit_speed_threshold: ENEMY_HIT_SPEED_THRESHOLD,
-            hit_height_threshold: 0.125 * ARENA_HEIGHT,
+            min_speed: config.min_speed,
+            max_speed: config.max_speed,
+            normal_speed: config.normal_speed,
+            damp: config.damp,
+            hit_range: config.hit_range,
+            hit_speed_threshold: config.hit_speed_threshold,
+            hit_height_threshold: config.hit_height_threshold,
+            reaction_latency: 0.0,
+            prediction_noise: 0.0,
+            decisions: VecDeque::new(),
+        }
+    }
+}
+
+impl Enemy {
+    pub fn new(preset: &DifficultyPreset) -> Self {
+        Self {
+            min_speed: preset.min_speed,
+            max_speed: preset.max_speed,
+            normal_speed: preset.normal_speed,
+            hit_range: preset.hit_range,
+            reaction_latency: preset.reaction_latency,
+            prediction_noise: preset.prediction_noise,
+            ..Default::default()
+        }
+    }
+
+    /// Takes the tuning of [`Enemy::new`] again, keeping the decisions in flight.
+    pub fn retune(&mut self, preset: &DifficultyPreset) {
+        let decisions = std::mem::take(&mut self.decisions);
+        *self = Self {
+            decisions,
+            ..Self::new(preset)
+        };
+    }
+
+    /// Queues the decided velocity, and returns the latest one that is old enough to act on.
+    pub fn react(&mut self, time: f32, velocity: Vec2) -> Vec2 {
+        self.decisions.push_back((time, velocity));
+        while self
+            .decisions
+            .get(1)
+            .is_some_and(|(decided, _)| *decided <= time - self.reaction_latency)
+        {
+            self.decisions.pop_front();
+        }
+
+        match self.decisions.front() {
+            Some((decided, velocity)) if *decided <= time - self.reaction_latency => *velocity,
+            _ => Vec2::ZERO,
         }
     }
 }
@@ -37,8 +95,7 @@ pub struct Controller {
 }
 
 pub fn move_enemy(
-    time: Res<Time>,
-    time_scale: Res<TimeScale>,
+    clock: Res<GameClock>,
     mut query: Query<(&Enemy, Option<&Controller>, &mut Motion)>,
 ) {
     for (enemy, controller, mut motion) in query.iter_mut() {
@@ -47,111 +104,73 @@ pub fn move_enemy(
             .unwrap_or_default();
         motion.velocity = motion
             .velocity
-            .damp(velocity, enemy.damp, time.delta_seconds() * time_scale.0)
+            .damp(velocity, enemy.damp, clock.scaled_delta_seconds())
             .clamp_length_max(enemy.max_speed);
     }
 }
 
+/// Picks the ball to play for each enemy, and lets its [`Brain`] decide how to move.
+#[allow(clippy::type_complexity)]
 pub fn control_enemy(
-    time: Res<Time>,
-    mut query: Query<(&Transform, &Enemy, &mut Controller), Without<Ball>>,
+    clock: Res<GameClock>,
+    mut rng: ResMut<GameRng>,
+    mut query: Query<(&Transform, &Motion, &mut Enemy, &mut Brain, &mut Controller), Without<Ball>>,
     ball_query: Query<(&Transform, &Motion, &Trajectory), With<Ball>>,
+    base_query: Query<(Entity, &EnemyBase)>,
 ) {
-    for (transform, enemy, mut controller) in query.iter_mut() {
-        controller.velocity = Vec2::ZERO;
-
-        for (ball_transform, motion, trajectory) in ball_query.iter() {
-            let direction = (ball_transform.translation - transform.translation).truncate();
-            let position = transform.translation.truncate();
-
-            let updated_velocity = if direction.x.abs() < enemy.hit_range.x
-                && direction.y > -enemy.hit_range.y
-                && direction.y < -0.0
-                && motion.velocity.y > enemy.hit_speed_threshold
-                && position.y > enemy.hit_height_threshold
-            {
-                // very close to the ball, reacts in maximum speed
-                enemy.max_speed * direction.normalize()
-            } else {
-                // find the most suitable trajectory point
-                let delta_seconds = time.elapsed_seconds() - trajectory.start_time;
-
-                if let Some(candidate) = trajectory
-                    .points
-                    .iter()
-                    .filter(|point| point.position.y > 0.0)
-                    .filter(|point| point.position.y < ARENA_HEIGHT * 0.375)
-                    .filter(|point| point.velocity.y > 0.0)
-                    .filter(|point| {
-                        // filter reachable points
-                        let time = point.time - delta_seconds;
-                        let distance = (point.position - position).length();
-                        time > distance / enemy.normal_speed
-                    })
-                    .min_by(|a, b| {
-                        let cost = |target: Vec2| {
-                            (target - position).length_squared()
-                                + (ARENA_HEIGHT / 2.0 - target.y).powi(2)
-                        };
-                        cost(a.position)
-                            .partial_cmp(&cost(b.position))
-                            .unwrap_or(std::cmp::Ordering::Equal)
-                    })
-                {
-                    // find candidate, move to it at proper speed
-                    let direction = candidate.position - position;
-                    let time = candidate.time - delta_seconds;
-                    let distance = direction.length();
-
-                    let mut speed =
-                        (distance / time + 1.0).clamp(enemy.min_speed, enemy.normal_speed);
-
-                    if distance < ENEMY_BRAKE_DISTANCE {
-                        speed *= distance / ENEMY_BRAKE_DISTANCE;
-                    }
-                    speed * direction.normalize()
-                } else {
-                    // not found, choose the average trajectory points as the candidate.
-                    let collection: Vec<_> = trajectory
-                        .points
-                        .iter()
-                        .map(|point| point.position)
-                        .filter(|position| position.y > -ARENA_HEIGHT / 2.0)
-                        .map(|position| {
-                            let mut contribution = position;
-                            if position.y < 0.0 {
-                                contribution.y = -position.y;
-                            }
-                            if motion.translation.y > transform.translation.y {
-                                contribution.x = -contribution.x;
-                            }
-                            contribution
-                        })
-                        .collect();
-
-                    let mut candidate: Vec2 = collection.iter().sum();
-                    candidate /= collection.len() as f32;
-                    candidate.y = candidate
-                        .y
-                        .clamp(0.125 * ARENA_HEIGHT, 0.375 * ARENA_HEIGHT);
-
-                    let direction = candidate - position;
-                    let distance = direction.length();
-                    let speed = if distance < ENEMY_BRAKE_DISTANCE {
-                        if motion.translation.y < 0.0 {
-                            0.0
-                        } else {
-                            enemy.normal_speed * distance / ENEMY_BRAKE_DISTANCE
-                        }
-                    } else {
-                        enemy.normal_speed
-                    };
-
-                    speed * direction.normalize()
-                }
-            };
-
-            controller.velocity += updated_velocity;
-        }
+    let elapsed_seconds = clock.elapsed_seconds();
+
+    // chase the ball that threatens the base the most, instead of the average of all balls
+    let target = ball_query.iter().min_by(|(_, a, x), (_, b, y)| {
+        threat(a, x, elapsed_seconds).total_cmp(&threat(b, y, elapsed_seconds))
+    });
+    let base = base_query
+        .get_single()
+        .ok()
+        .map(|(entity, base)| BaseSnapshot {
+            entity,
+            health: base.hp,
+            full_health: base.full_hp,
+        });
+    let rng = rng.gameplay();
+
+    for (transform, motion, mut enemy, mut brain, mut controller) in query.iter_mut() {
+        let snapshot = EnemySnapshot {
+            enemy: &enemy,
+            transform,
+            motion,
+            ball: target.map(|(transform, motion, trajectory)| BallSnapshot {
+                transform,
+                motion,
+                trajectory,
+            }),
+            base,
+            elapsed_seconds,
+            rng,
+        };
+        let velocity = brain.0.think(&snapshot);
+
+        controller.velocity = enemy.react(elapsed_seconds, velocity);
     }
 }
+
+/// Estimates the time before the ball reaches the enemy's half on its way up.
+/// Balls that are not predicted to get there rank after, the closer to the enemy's half the sooner.
+pub fn threat(motion: &Motion, trajectory: &Trajectory, elapsed_seconds: f32) -> f32 {
+    if motion.translation.y > 0.0 && motion.velocity.y > 0.0 {
+        return 0.0;
+    }
+
+    let delta_seconds = elapsed_seconds - trajectory.start_time;
+    trajectory
+        .points
+        .iter()
+        .find(|point| point.position.y > 0.0 && point.velocity.y > 0.0)
+        .map_or_else(
+            || {
+                let horizon = PREDICT_SIZE as f32 * PREDICT_TIME_STEP;
+                horizon + 0.5 - motion.translation.y / ARENA_HEIGHT
+            },
+            |point| point.time - delta_seconds,
+        )
+}
@@ -1,27 +1,56 @@
 use self::{
-    ball::*, base::*, battle::*, enemy::*, hint::*, physics::*, player::*, practice::*, slits::*,
+    autopilot::*, ball::*, base::*, battle::*, enemy::*, hint::*, physics::*, player::*,
+    practice::*, slits::*, versus::*,
 };
 use crate::{
     constants::*,
     effects::*,
-    utils::{cleanup_system, escape_system, Damp, Intermediate},
-    AppState, AudioVolume, MusicTrack, TimeScale,
+    input::{read_control_input, read_player_two_input, PlayerTwoInput},
+    loading::AssetMap,
+    utils::{
+        cleanup_system, fixed_step, pause_system, unpaused, update_game_clock, Damp, FixedSteps,
+        GameClock, Intermediate, Shape,
+    },
+    AppState, AudioVolume, GameRng, MusicTrack, TimeScale,
 };
-use bevy::{prelude::*, sprite::MaterialMesh2dBundle, time::FixedTimestep};
+use bevy::{prelude::*, sprite::MaterialMesh2dBundle, time::TimeSystem};
 use bevy_kira_audio::{Audio, AudioApp, AudioChannel, AudioControl, AudioSource};
-use itertools::Itertools;
 use std::f32::consts::FRAC_PI_4;
 
+mod autopilot;
 mod ball;
 mod base;
 mod battle;
+mod brain;
+mod difficulty;
 mod enemy;
 mod hint;
 mod physics;
 mod player;
 mod practice;
 mod slits;
+mod versus;
+
+pub use self::{
+    autopilot::{Autopilot, SelfPlay},
+    ball::{Ball, Point, Trajectory},
+    base::{EnemyBase, PlayerBase},
+    brain::{
+        BallSnapshot, BaseSnapshot, Brain, BrainKind, EnemyBrain, EnemySnapshot, HeuristicBrain,
+        SmasherBrain, TrackerBrain,
+    },
+    difficulty::{Difficulty, DifficultyPreset},
+    enemy::Enemy,
+    physics::{
+        BroadPhase, MaxTimeOfImpacts, Motion, PhysicsLayers, PhysicsPlugin, PhysicsQuery, QueryHit,
+        RigidBody, Sensor, TriggerEnter, TriggerExit, TriggerStay,
+    },
+    player::{read_player_input, Assist, Player, PlayerAssist, PlayerInput, PlayerTwo},
+    versus::{GameMode, Winner},
+};
 
+/// Game-play systems, physics and game modes.
+/// Renders nothing and plays no audio, so it can run without a window (see [`crate::headless`]).
 pub struct GamePlugin;
 
 impl Plugin for GamePlugin {
@@ -31,42 +60,73 @@ impl Plugin for GamePlugin {
             .add_event::<PlayerMissEvent>()
             .add_event::<BounceEvent>()
             .add_event::<HealEvent>()
-            .insert_resource(Debounce {
-                audio_bounce_long: Timer::from_seconds(0.5, TimerMode::Once),
-                audio_bounce_short: Timer::from_seconds(0.1, TimerMode::Once),
-                audio_hit: Timer::from_seconds(0.1, TimerMode::Once),
-                bounce: Timer::from_seconds(0.1, TimerMode::Once),
-                effects: Timer::from_seconds(0.1, TimerMode::Once),
-                hit: Timer::from_seconds(0.1, TimerMode::Once),
-                miss: Timer::from_seconds(0.5, TimerMode::Once),
-            })
+            .init_resource::<GameClock>()
             .init_resource::<Score>()
+            .init_resource::<MultiBall>()
+            .init_resource::<Difficulty>()
+            .init_resource::<Assist>()
+            .init_resource::<BrainKind>()
+            .init_resource::<SelfPlay>()
             .init_resource::<Slits>()
-            .add_audio_channel::<BounceAudioChannel>()
-            .add_audio_channel::<ScoreAudioChannel>()
-            .add_startup_system(setup_game)
+            .init_resource::<FixedSteps>()
+            .init_resource::<PlayerInput>()
+            .add_system_to_stage(CoreStage::First, update_game_clock.after(TimeSystem))
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                read_player_input
+                    .after(read_control_input)
+                    .after(read_player_two_input),
+            )
             .add_system_set(
                 SystemSet::new()
+                    .with_run_criteria(unpaused)
                     // fundamental game-play systems
                     .with_system(move_player)
                     .with_system(assist_player)
                     .with_system(move_enemy)
                     .with_system(move_ball)
+                    .with_system(spin_ball)
                     .with_system(activate_ball)
-                    .with_system(update_ball)
                     .with_system(ball_bounce)
                     .with_system(heal_enemy_base)
                     .with_system(move_slit_block)
                     .with_system(slits_system)
-                    // effects and juice
                     .with_system(game_over_slow_motion)
+                    .with_system(score_system),
+            )
+            .add_system_set(
+                SystemSet::new()
+                    .with_run_criteria(fixed_step("ai", AI_TIME_STEP))
+                    .with_system(predict_ball)
+                    .with_system(control_enemy)
+                    .with_system(control_autopilot.after(control_enemy)),
+            )
+            .add_plugin(PhysicsPlugin)
+            .add_plugin(BattlePlugin)
+            .add_plugin(PracticePlugin)
+            .add_plugin(VersusPlugin);
+    }
+}
+
+/// Sprites, audio, effects and UI of the game.
+/// Requires [`GamePlugin`] and the rendering and audio plugins.
+pub struct PresentationPlugin;
+
+impl Plugin for PresentationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_audio_channel::<BounceAudioChannel>()
+            .add_audio_channel::<ScoreAudioChannel>()
+            .add_system_set(
+                SystemSet::new()
+                    .with_run_criteria(unpaused)
+                    .with_system(update_ball)
+                    // effects and juice
                     .with_system(bounce_audio)
                     .with_system(score_audio)
                     .with_system(score_effects)
                     .with_system(bounce_effects)
                     // score and display
                     .with_system(count_ball)
-                    .with_system(score_system)
                     .with_system(health_bar)
                     .with_system(health_bar_tracker)
                     // hints
@@ -75,14 +135,21 @@ impl Plugin for GamePlugin {
                     .with_system(hint_system),
             )
             .add_system_set(
-                SystemSet::new()
-                    .with_run_criteria(FixedTimestep::step(AI_TIME_STEP as f64))
-                    .with_system(predict_ball)
-                    .with_system(control_enemy),
+                SystemSet::on_enter(AppState::Battle)
+                    .with_system(make_ui)
+                    .with_system(play_game_music),
             )
-            .add_plugin(PhysicsPlugin)
-            .add_plugin(BattlePlugin)
-            .add_plugin(PracticePlugin);
+            .add_system_set(
+                SystemSet::on_enter(AppState::Practice)
+                    .with_system(make_ui)
+                    .with_system(play_game_music),
+            )
+            .add_system_set(
+                SystemSet::on_enter(AppState::Versus)
+                    .with_system(make_ui)
+                    .with_system(make_player_two_ui)
+                    .with_system(play_game_music),
+            );
     }
 }
 
@@ -92,8 +159,8 @@ struct BounceAudioChannel;
 #[derive(Resource)]
 struct ScoreAudioChannel;
 
-#[derive(Clone, Copy)]
-enum GameOverEvent {
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameOverEvent {
     Win,
     Lose,
 }
@@ -117,18 +184,56 @@ struct BounceEvent {
     location: Vec2,
 }
 
-#[derive(Resource)]
+/// Debouncing state of a single ball, so that balls do not mute each other.
+#[derive(Component)]
 struct Debounce {
     audio_bounce_long: Timer,
     audio_bounce_short: Timer,
     audio_hit: Timer,
+    audio_entities: Option<[Entity; 2]>,
 
     bounce: Timer,
     effects: Timer,
+    effects_entities: Option<[Entity; 2]>,
     hit: Timer,
     miss: Timer,
 }
 
+impl Default for Debounce {
+    fn default() -> Self {
+        Self {
+            audio_bounce_long: Timer::from_seconds(0.5, TimerMode::Once),
+            audio_bounce_short: Timer::from_seconds(0.1, TimerMode::Once),
+            audio_hit: Timer::from_seconds(0.1, TimerMode::Once),
+            audio_entities: None,
+            bounce: Timer::from_seconds(0.1, TimerMode::Once),
+            effects: Timer::from_seconds(0.1, TimerMode::Once),
+            effects_entities: None,
+            hit: Timer::from_seconds(0.1, TimerMode::Once),
+            miss: Timer::from_seconds(0.5, TimerMode::Once),
+        }
+    }
+}
+
+/// Spawns an extra ball in battle every `milestone` hits, up to `max_count` balls in play.
+/// Off unless the player turns it on in the settings.
+#[derive(Resource)]
+pub struct MultiBall {
+    pub enabled: bool,
+    pub milestone: i32,
+    pub max_count: usize,
+}
+
+impl Default for MultiBall {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            milestone: MULTI_BALL_MILESTONE,
+            max_count: MULTI_BALL_MAX_COUNT,
+        }
+    }
+}
+
 #[derive(Resource)]
 struct GameOver {
     slow_motion_timer: Timer,
@@ -151,22 +256,42 @@ impl Default for GameOver {
 
 #[derive(Resource)]
 pub struct Score {
+    /// [`GameClock::scaled_elapsed_seconds`] when the run started.
     pub timestamp: f32,
     pub hits: i32,
     pub miss: i32,
+    /// Balls left in the [`PlayerBase`].
+    pub ball_count: i32,
 }
 
 impl FromWorld for Score {
     fn from_world(world: &mut World) -> Self {
-        let time = world.resource::<Time>();
+        let clock = world.resource::<GameClock>();
         Self {
-            timestamp: time.elapsed_seconds(),
+            timestamp: clock.scaled_elapsed_seconds(),
             hits: 0,
             miss: 0,
+            ball_count: PLAYER_BASE_BALL_COUNT,
         }
     }
 }
 
+impl Score {
+    /// Game time since the run started, without the pauses and slowed down by slow motion.
+    pub fn time(&self, clock: &GameClock) -> f32 {
+        clock.scaled_elapsed_seconds() - self.timestamp
+    }
+
+    /// Composite score of a won run that took `time` seconds, which ranks the high score table.
+    pub fn points(&self, time: f32) -> i32 {
+        let time_bonus = (SCORE_TIME_BONUS - SCORE_TIME_PENALTY * time).max(0.0) as i32;
+        (SCORE_HIT_POINTS * self.hits + SCORE_BALL_POINTS * self.ball_count
+            - SCORE_MISS_PENALTY * self.miss
+            + time_bonus)
+            .max(0)
+    }
+}
+
 #[derive(Component)]
 struct Cleanup;
 
@@ -176,61 +301,30 @@ enum BounceAudio {
     Hit,
 }
 
-#[derive(Resource)]
-struct Materials {
-    player: Handle<Image>,
-    enemy: Handle<Image>,
-    ball: Handle<Image>,
-    hint: Handle<Image>,
-    death: Handle<Image>,
-    hit: Handle<TextureAtlas>,
-}
+fn play_game_music(
+    audios: Res<AssetMap<AudioSource>>,
+    audio: Res<Audio>,
+    volume: Res<AudioVolume>,
+    mut music_track: ResMut<MusicTrack>,
+) {
+    if music_track.0 != "game_music" {
+        audio.stop();
+        audio.set_volume(volume.music.into());
+        audio.set_playback_rate(1.2);
+        // music may have failed to load
+        if let Some(music) = audios.try_get("game_music") {
+            audio.play(music).looped();
+        }
 
-#[derive(Resource)]
-struct Audios {
-    hit_audio: Handle<AudioSource>,
-    miss_audio: Handle<AudioSource>,
-    explosion_audio: Handle<AudioSource>,
-    lose_audio: Handle<AudioSource>,
-    impact_audios: Vec<Handle<AudioSource>>,
+        music_track.0 = "game_music";
+    }
 }
 
-fn setup_game(
+fn make_arena(
     mut commands: Commands,
-    asset_server: Res<AssetServer>,
-    mut texture_atlases: ResMut<Assets<TextureAtlas>>,
+    app_state: Res<State<AppState>>,
+    difficulty: Res<Difficulty>,
 ) {
-    commands.insert_resource(Materials {
-        player: asset_server.load(PLAYER_SPRITE),
-        enemy: asset_server.load(ENEMY_SPRITE),
-        ball: asset_server.load(BALL_SPRITE),
-        hint: asset_server.load(HINT_SPRITE),
-        death: asset_server.load(DEATH_SPRITE),
-        hit: texture_atlases.add(TextureAtlas::from_grid(
-            asset_server.load(HIT_SPRITE),
-            Vec2::new(1024.0, 1024.0),
-            4,
-            4,
-            None,
-            None,
-        )),
-    });
-
-    commands.insert_resource(Audios {
-        hit_audio: asset_server.load(HIT_AUDIO),
-        miss_audio: asset_server.load(MISS_AUDIO),
-        explosion_audio: asset_server.load(EXPLOSION_AUDIO),
-        lose_audio: asset_server.load(LOSE_AUDIO),
-        impact_audios: IMPACT_AUDIOS
-            .iter()
-            .map(|path| asset_server.load(*path))
-            .collect_vec(),
-    });
-
-    commands.init_resource::<Score>();
-}
-
-fn make_arena(mut commands: Commands) {
     // middle Separate
     commands
         .spawn((
@@ -243,7 +337,7 @@ fn make_arena(mut commands: Commands) {
                 },
                 ..Default::default()
             },
-            RigidBody::new(Vec2::new(ARENA_WIDTH, 32.0), 0.0, 0.9, 0.5),
+            RigidBody::new(Shape::Box(Vec2::new(ARENA_WIDTH, 32.0)), 0.0, 0.9, 0.5),
             PhysicsLayers::SEPARATE,
             Cleanup,
         ))
@@ -259,23 +353,38 @@ fn make_arena(mut commands: Commands) {
             });
         });
 
-    // top boundary
-    commands.spawn((
-        SpriteBundle {
-            transform: Transform::from_xyz(0.0, ARENA_HEIGHT * 0.5 + 16.0, 0.0),
-            sprite: Sprite {
-                color: BOUNDARY_COLOR,
-                custom_size: Some(Vec2::new(ARENA_WIDTH, 32.0)),
-                ..Default::default()
-            },
+    // top boundary, the base of player two in versus
+    let top_sprite = SpriteBundle {
+        transform: Transform::from_xyz(0.0, ARENA_HEIGHT * 0.5 + 16.0, 0.0),
+        sprite: Sprite {
+            color: BOUNDARY_COLOR,
+            custom_size: Some(Vec2::new(ARENA_WIDTH, 32.0)),
             ..Default::default()
         },
-        RigidBody::new(Vec2::new(ARENA_WIDTH, 32.0), 0.0, 0.9, 0.0),
-        PhysicsLayers::BOUNDARY,
-        BounceAudio::Hit,
-        EnemyBase::default(),
-        Cleanup,
-    ));
+        ..Default::default()
+    };
+    if *app_state.current() == AppState::Versus {
+        commands.spawn((
+            top_sprite,
+            RigidBody::new(Shape::Box(Vec2::new(ARENA_WIDTH, 32.0)), 0.0, 0.9, 0.5),
+            PhysicsLayers::BOUNDARY,
+            PlayerBase::default(),
+            PlayerTwo,
+            Cleanup,
+        ));
+    } else {
+        commands.spawn((
+            top_sprite,
+            RigidBody::new(Shape::Box(Vec2::new(ARENA_WIDTH, 32.0)), 0.0, 0.9, 0.0),
+            PhysicsLayers::BOUNDARY,
+            BounceAudio::Hit,
+            EnemyBase {
+                full_hp: difficulty.preset().enemy_base_hp,
+                ..Default::default()
+            },
+            Cleanup,
+        ));
+    }
 
     // bottom boundary
     commands.spawn((
@@ -288,7 +397,7 @@ fn make_arena(mut commands: Commands) {
             },
             ..Default::default()
         },
-        RigidBody::new(Vec2::new(ARENA_WIDTH, 32.0), 0.0, 0.9, 0.5),
+        RigidBody::new(Shape::Box(Vec2::new(ARENA_WIDTH, 32.0)), 0.0, 0.9, 0.5),
         PhysicsLayers::BOUNDARY,
         PlayerBase::default(),
         Cleanup,
@@ -305,7 +414,12 @@ fn make_arena(mut commands: Commands) {
             },
             ..Default::default()
         },
-        RigidBody::new(Vec2::new(32.0, ARENA_HEIGHT + 64.0), 0.0, 1.0, 0.0),
+        RigidBody::new(
+            Shape::Box(Vec2::new(32.0, ARENA_HEIGHT + 64.0)),
+            0.0,
+            1.0,
+            0.0,
+        ),
         PhysicsLayers::BOUNDARY,
         BounceAudio::Bounce,
         Cleanup,
@@ -322,14 +436,19 @@ fn make_arena(mut commands: Commands) {
             },
             ..Default::default()
         },
-        RigidBody::new(Vec2::new(32.0, ARENA_HEIGHT + 64.0), 0.0, 1.0, 0.0),
+        RigidBody::new(
+            Shape::Box(Vec2::new(32.0, ARENA_HEIGHT + 64.0)),
+            0.0,
+            1.0,
+            0.0,
+        ),
         PhysicsLayers::BOUNDARY,
         BounceAudio::Bounce,
         Cleanup,
     ));
 }
 
-fn make_ui(mut commands: Commands, materials: Res<Materials>, asset_server: Res<AssetServer>) {
+fn make_ui(mut commands: Commands, images: Res<AssetMap<Image>>, fonts: Res<AssetMap<Font>>) {
     commands
         .spawn((
             NodeBundle {
@@ -396,7 +515,7 @@ fn make_ui(mut commands: Commands, materials: Res<Materials>, asset_server: Res<
                     size: Size::new(Val::Px(16.0), Val::Px(16.0)),
                     ..Default::default()
                 },
-                image: materials.ball.clone().into(),
+                image: images.get("ball").into(),
                 ..Default::default()
             });
 
@@ -407,7 +526,7 @@ fn make_ui(mut commands: Commands, materials: Res<Materials>, asset_server: Res<
                             TextSection {
                                 value: " x ".into(),
                                 style: TextStyle {
-                                    font: asset_server.load(FONT_FIRA_MONO),
+                                    font: fonts.get("fira_mono"),
                                     font_size: 20.0,
                                     color: Color::WHITE,
                                 },
@@ -415,7 +534,7 @@ fn make_ui(mut commands: Commands, materials: Res<Materials>, asset_server: Res<
                             TextSection {
                                 value: "".into(),
                                 style: TextStyle {
-                                    font: asset_server.load(FONT_FIRA_MONO),
+                                    font: fonts.get("fira_mono"),
                                     font_size: 20.0,
                                     color: Color::WHITE,
                                 },
@@ -430,102 +549,205 @@ fn make_ui(mut commands: Commands, materials: Res<Materials>, asset_server: Res<
         });
 }
 
-fn make_player(mut commands: Commands, materials: Res<Materials>) {
+/// Ball counter of player two in versus, at the top of the arena.
+fn make_player_two_ui(
+    mut commands: Commands,
+    images: Res<AssetMap<Image>>,
+    fonts: Res<AssetMap<Font>>,
+) {
     commands
         .spawn((
-            SpriteBundle {
-                transform: Transform::from_xyz(0.0, -160.0, 0.0),
-                sprite: Sprite {
-                    custom_size: Some(Vec2::new(PADDLE_WIDTH, PADDLE_HEIGHT)),
-                    color: PADDLE_COLOR,
+            NodeBundle {
+                style: Style {
+                    size: Size::new(Val::Percent(100.0), Val::Px(16.0)),
+                    position_type: PositionType::Absolute,
+                    position: UiRect {
+                        left: Val::Px(16.0),
+                        top: Val::Px(16.0),
+                        ..Default::default()
+                    },
                     ..Default::default()
                 },
+                background_color: Color::NONE.into(),
                 ..Default::default()
             },
-            RigidBody::new(Vec2::new(PADDLE_WIDTH, PADDLE_HEIGHT), 3.0, 2.0, 1.0),
-            Motion::default(),
-            PhysicsLayers::PLAYER,
-            BounceAudio::Bounce,
-            Controller::default(),
-            MotionOverride::default(),
-            Player::default(),
-            PlayerAssist::default(),
             Cleanup,
         ))
         .with_children(|parent| {
+            parent.spawn(ImageBundle {
+                style: Style {
+                    size: Size::new(Val::Px(16.0), Val::Px(16.0)),
+                    ..Default::default()
+                },
+                image: images.get("ball").into(),
+                ..Default::default()
+            });
+
+            parent.spawn((
+                TextBundle {
+                    text: Text {
+                        sections: vec![
+                            TextSection {
+                                value: " x ".into(),
+                                style: TextStyle {
+                                    font: fonts.get("fira_mono"),
+                                    font_size: 20.0,
+                                    color: Color::WHITE,
+                                },
+                            },
+                            TextSection {
+                                value: "".into(),
+                                style: TextStyle {
+                                    font: fonts.get("fira_mono"),
+                                    font_size: 20.0,
+                                    color: Color::WHITE,
+                                },
+                            },
+                        ],
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                PlayerTwoBallCounter,
+            ));
+        });
+}
+
+fn make_player(
+    mut commands: Commands,
+    images: Option<Res<AssetMap<Image>>>,
+    self_play: Res<SelfPlay>,
+) {
+    let mut player = commands.spawn((
+        SpriteBundle {
+            transform: Transform::from_xyz(0.0, -160.0, 0.0),
+            sprite: Sprite {
+                custom_size: Some(Vec2::new(PADDLE_WIDTH, PADDLE_HEIGHT)),
+                color: PADDLE_COLOR,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        RigidBody::new(
+            Shape::Box(Vec2::new(PADDLE_WIDTH, PADDLE_HEIGHT)),
+            3.0,
+            2.0,
+            1.0,
+        ),
+        Motion::default(),
+        PhysicsLayers::PLAYER,
+        BounceAudio::Bounce,
+        Controller::default(),
+        MotionOverride::default(),
+        Player::default(),
+        PlayerAssist::default(),
+        Cleanup,
+    ));
+
+    if let Some(brain) = self_play.brain {
+        player.insert(Autopilot::new(brain, self_play.difficulty));
+    }
+
+    if let Some(images) = images {
+        player.with_children(|parent| {
             parent.spawn(SpriteBundle {
                 transform: Transform::from_xyz(-PADDLE_WIDTH / 2.0 + 8.0, 0.0, 0.1),
-                texture: materials.player.clone(),
+                texture: images.get("player"),
                 ..Default::default()
             });
 
             parent.spawn(SpriteBundle {
                 transform: Transform::from_xyz(PADDLE_WIDTH / 2.0 - 8.0, 0.0, 0.1),
-                texture: materials.player.clone(),
+                texture: images.get("player"),
                 ..Default::default()
             });
         });
+    }
 }
 
-fn make_enemy(mut commands: Commands, materials: Res<Materials>) {
-    commands
-        .spawn((
-            SpriteBundle {
-                transform: Transform::from_xyz(0.0, 160.0, 0.0),
-                sprite: Sprite {
-                    custom_size: Some(Vec2::new(PADDLE_WIDTH, PADDLE_HEIGHT)),
-                    color: PADDLE_COLOR,
-                    ..Default::default()
-                },
+fn make_enemy(
+    mut commands: Commands,
+    images: Option<Res<AssetMap<Image>>>,
+    difficulty: Res<Difficulty>,
+    brain_kind: Res<BrainKind>,
+) {
+    let mut enemy = commands.spawn((
+        SpriteBundle {
+            transform: Transform::from_xyz(0.0, 160.0, 0.0),
+            sprite: Sprite {
+                custom_size: Some(Vec2::new(PADDLE_WIDTH, PADDLE_HEIGHT)),
+                color: PADDLE_COLOR,
                 ..Default::default()
             },
-            RigidBody::new(Vec2::new(PADDLE_WIDTH, PADDLE_HEIGHT), 3.0, 1.0, 1.0),
-            Motion::default(),
-            PhysicsLayers::PLAYER,
-            BounceAudio::Bounce,
-            Controller::default(),
-            Enemy::default(),
-            Cleanup,
-        ))
-        .with_children(|parent| {
+            ..Default::default()
+        },
+        RigidBody::new(
+            Shape::Box(Vec2::new(PADDLE_WIDTH, PADDLE_HEIGHT)),
+            3.0,
+            1.0,
+            1.0,
+        ),
+        Motion::default(),
+        PhysicsLayers::PLAYER,
+        BounceAudio::Bounce,
+        Controller::default(),
+        Enemy::new(&difficulty.preset()),
+        brain_kind.brain(),
+        Cleanup,
+    ));
+
+    if let Some(images) = images {
+        enemy.with_children(|parent| {
             parent.spawn(SpriteBundle {
                 transform: Transform::from_xyz(-PADDLE_WIDTH / 2.0 + 8.0, 0.0, 0.1),
-                texture: materials.enemy.clone(),
+                texture: images.get("enemy"),
                 ..Default::default()
             });
 
             parent.spawn(SpriteBundle {
                 transform: Transform::from_xyz(PADDLE_WIDTH / 2.0 - 8.0, 0.0, 0.1),
-                texture: materials.enemy.clone(),
+                texture: images.get("enemy"),
                 ..Default::default()
             });
         });
+    }
 }
 
-fn make_ball(mut commands: Commands, materials: Res<Materials>) {
+fn make_ball(mut commands: Commands, images: Option<Res<AssetMap<Image>>>) {
+    spawn_ball(&mut commands, images.as_deref(), Vec2::ZERO);
+}
+
+fn spawn_ball(commands: &mut Commands, images: Option<&AssetMap<Image>>, origin: Vec2) {
     let alpha = 1.0 / BALL_GHOSTS_COUNT as f32;
-    commands
-        .spawn((
-            SpriteBundle {
-                transform: Transform::from_xyz(0.0, 0.0, -1.0),
-                texture: materials.ball.clone(),
-                sprite: Sprite {
-                    color: Color::rgba(1.0, 1.0, 1.0, alpha),
-                    ..Default::default()
-                },
+    let texture = images.map(|images| images.get("ball")).unwrap_or_default();
+    let mut ball = commands.spawn((
+        SpriteBundle {
+            transform: Transform::from_translation(origin.extend(-1.0)),
+            texture: texture.clone(),
+            sprite: Sprite {
+                color: Color::rgba(1.0, 1.0, 1.0, alpha),
                 ..Default::default()
             },
-            RigidBody::new(Vec2::new(BALL_SIZE, BALL_SIZE), 1.0, 1.0, 0.5),
-            PhysicsLayers::BALL,
-            BounceAudio::Bounce,
-            Ball::default(),
-            Trajectory::default(),
-            Cleanup,
-        ))
-        .with_children(|parent| {
+            ..Default::default()
+        },
+        RigidBody::new(Shape::Circle(BALL_SIZE / 2.0), 1.0, 1.0, 0.5),
+        PhysicsLayers::BALL,
+        BounceAudio::Bounce,
+        Ball {
+            origin,
+            ..Default::default()
+        },
+        Debounce::default(),
+        Trajectory::default(),
+        Cleanup,
+    ));
+
+    // motion blur ghosts, see `update_ball`
+    if images.is_some() {
+        ball.with_children(|parent| {
             for _ in 0..BALL_GHOSTS_COUNT {
                 parent.spawn(SpriteBundle {
-                    texture: materials.ball.clone(),
+                    texture: texture.clone(),
                     sprite: Sprite {
                         color: Color::rgba(1.0, 1.0, 1.0, alpha),
                         ..Default::default()
@@ -534,6 +756,29 @@ fn make_ball(mut commands: Commands, materials: Res<Materials>) {
                 });
             }
         });
+    }
+}
+
+/// Spawns an extra ball each time the hits reach a milestone of [`MultiBall`].
+/// Extra balls are set aside the center, alternating left and right.
+fn multi_ball(
+    mut commands: Commands,
+    multi_ball: Res<MultiBall>,
+    score: Res<Score>,
+    images: Option<Res<AssetMap<Image>>>,
+    query: Query<(), With<Ball>>,
+) {
+    if !multi_ball.enabled || multi_ball.milestone <= 0 || !score.is_changed() {
+        return;
+    }
+
+    let target = (1 + (score.hits / multi_ball.milestone) as usize).min(multi_ball.max_count);
+    for index in query.iter().count()..target {
+        let side = if index % 2 == 0 { -1.0 } else { 1.0 };
+        let offset = index.div_ceil(2) as f32 * MULTI_BALL_SPACING;
+        let origin = Vec2::new(side * offset, 0.0);
+        spawn_ball(&mut commands, images.as_deref(), origin);
+    }
 }
 
 #[allow(clippy::type_complexity)]
@@ -542,11 +787,11 @@ fn reset_ball(
     mut player_miss_events: EventReader<PlayerMissEvent>,
     mut player_hit_events: EventReader<PlayerHitEvent>,
     mut time_scale: ResMut<TimeScale>,
-    mut query: Query<(Entity, &mut Transform), (With<Ball>, With<Motion>)>,
+    mut query: Query<(Entity, &Ball, &mut Transform), With<Motion>>,
 ) {
     let mut closure = |ball| -> Option<()> {
-        let (_, mut transform) = query.get_mut(ball).ok()?;
-        transform.translation = Vec3::new(0.0, 0.0, -1.0);
+        let (_, ball_data, mut transform) = query.get_mut(ball).ok()?;
+        transform.translation = ball_data.origin.extend(-1.0);
         commands.entity(ball).remove::<Motion>();
 
         // also reset time scale
@@ -566,48 +811,47 @@ fn reset_ball(
     }
 
     // reset if the ball if out of range
-    for (entity, mut transform) in query.iter_mut() {
+    for (entity, ball, mut transform) in query.iter_mut() {
         if transform.translation.x < -ARENA_WIDTH / 2.0
             || transform.translation.x > ARENA_WIDTH / 2.0
             || transform.translation.y < -ARENA_HEIGHT / 2.0
             || transform.translation.y > ARENA_HEIGHT / 2.0
         {
-            transform.translation = Vec3::new(0.0, 0.0, -1.0);
+            transform.translation = ball.origin.extend(-1.0);
             commands.entity(entity).remove::<Motion>();
         }
     }
 }
 
+/// Takes all balls out of play once the game is over.
 fn remove_ball(
     mut commands: Commands,
     mut player_miss_events: EventReader<PlayerMissEvent>,
     mut player_hit_events: EventReader<PlayerHitEvent>,
-    query: Query<(), With<Ball>>,
+    query: Query<Entity, With<Ball>>,
 ) {
-    for event in player_miss_events.iter() {
-        if event.lose && query.contains(event.ball) {
-            commands.entity(event.ball).remove::<Ball>();
-        }
-    }
+    let lose = player_miss_events.iter().any(|event| event.lose);
+    let win = player_hit_events.iter().any(|event| event.win);
 
-    for event in player_hit_events.iter() {
-        if event.win && query.contains(event.ball) {
-            commands.entity(event.ball).remove::<Ball>();
+    if lose || win {
+        for entity in query.iter() {
+            commands.entity(entity).remove::<Ball>();
         }
     }
 }
 
+#[allow(clippy::type_complexity)]
 fn make_player_hint(
     mut commands: Commands,
-    materials: Res<Materials>,
-    query: Query<Entity, (Added<Player>, Without<Hint>)>,
+    images: Res<AssetMap<Image>>,
+    query: Query<Entity, (Added<Player>, Without<PlayerTwo>, Without<Hint>)>,
 ) {
     for entity in query.iter() {
         let hint = commands
             .spawn((
                 SpriteBundle {
                     transform: Transform::from_xyz(0.0, ARENA_HEIGHT / 2.0, 0.0),
-                    texture: materials.hint.clone(),
+                    texture: images.get("hint"),
                     sprite: Sprite {
                         color: HINT_COLOR,
                         ..Default::default()
@@ -624,7 +868,7 @@ fn make_player_hint(
 
 fn make_ball_hint(
     mut commands: Commands,
-    materials: Res<Materials>,
+    images: Res<AssetMap<Image>>,
     query: Query<Entity, (Added<Ball>, Without<Hint>)>,
 ) {
     for entity in query.iter() {
@@ -632,7 +876,7 @@ fn make_ball_hint(
             .spawn((
                 SpriteBundle {
                     transform: Transform::from_xyz(0.0, -ARENA_HEIGHT / 2.0, 0.0),
-                    texture: materials.hint.clone(),
+                    texture: images.get("hint"),
                     sprite: Sprite {
                         color: HINT_COLOR,
                         ..Default::default()
@@ -651,97 +895,112 @@ fn make_ball_hint(
 /// Sends [`PlayerHitEvent`] and [`GameOverEvent::Win`].
 #[allow(clippy::too_many_arguments)]
 fn player_hit(
-    time: Res<Time>,
-    mut timer: ResMut<Debounce>,
+    clock: Res<GameClock>,
     mut collision_events: EventReader<CollisionEvent>,
     mut player_hit_events: EventWriter<PlayerHitEvent>,
     mut game_over_events: EventWriter<GameOverEvent>,
-    ball_query: Query<(&RigidBody, &Motion), With<Ball>>,
+    mut ball_query: Query<(&RigidBody, &Motion, &mut Debounce), With<Ball>>,
     mut base_query: Query<&mut EnemyBase, Without<Ball>>,
 ) {
-    if timer.hit.tick(time.delta()).finished() {
-        for event in collision_events.iter() {
-            let mut closure = |ball: Entity, base: Entity| -> Option<()> {
-                let (rigid_body, motion) = ball_query.get(ball).ok()?;
-                let mut base = base_query.get_mut(base).ok()?;
-
-                let location = event.hit.location();
-                let hp = base.hp;
-
-                let mass = rigid_body.mass();
-                let speed = motion.velocity.length();
-                let damage = hp.min(speed * mass).min(MAX_DAMAGE);
-
-                base.hp -= damage;
-
-                let win = base.hp <= 0.0;
-                if win {
-                    game_over_events.send(GameOverEvent::Win);
-                }
-                timer.hit.reset();
-
-                player_hit_events.send(PlayerHitEvent {
-                    ball,
-                    location,
-                    win,
-                });
+    for (_, _, mut debounce) in ball_query.iter_mut() {
+        debounce.hit.tick(clock.delta());
+    }
 
-                Some(())
-            };
+    for event in collision_events.iter() {
+        let mut closure = |ball: Entity, base: Entity| -> Option<()> {
+            let (rigid_body, motion, mut debounce) = ball_query.get_mut(ball).ok()?;
+            let mut base = base_query.get_mut(base).ok()?;
+            if !debounce.hit.finished() {
+                return Some(());
+            }
 
-            closure(event.entities[0], event.entities[1])
-                .or_else(|| closure(event.entities[1], event.entities[0]));
-        }
+            let location = event.hit.location();
+            let hp = base.hp;
+
+            let mass = rigid_body.mass();
+            let speed = motion.velocity.length();
+            let damage = hp.min(speed * mass).min(MAX_DAMAGE);
+
+            base.hp -= damage;
+
+            let win = base.hp <= 0.0;
+            if win {
+                game_over_events.send(GameOverEvent::Win);
+            }
+            debounce.hit.reset();
+
+            player_hit_events.send(PlayerHitEvent {
+                ball,
+                location,
+                win,
+            });
+
+            Some(())
+        };
+
+        closure(event.entities[0], event.entities[1])
+            .or_else(|| closure(event.entities[1], event.entities[0]));
     }
 }
 
 /// Decreases player's ball count when the ball hits player's base.
-/// Sends [`PlayerMissEvent`] and [`GameOverEvent::Lose`].
+/// Sends [`PlayerMissEvent`] and [`GameOverEvent::Lose`], or [`GameOverEvent::Win`] when player two loses in versus.
 #[allow(clippy::too_many_arguments)]
 fn player_miss(
-    time: Res<Time>,
-    mut timer: ResMut<Debounce>,
+    clock: Res<GameClock>,
     mut collision_events: EventReader<CollisionEvent>,
     mut player_miss_events: EventWriter<PlayerMissEvent>,
     mut game_over_events: EventWriter<GameOverEvent>,
-    ball_query: Query<(), With<Ball>>,
-    mut base_query: Query<&mut PlayerBase, Without<Ball>>,
+    mut score: ResMut<Score>,
+    mut ball_query: Query<&mut Debounce, With<Ball>>,
+    mut base_query: Query<(&mut PlayerBase, Option<&PlayerTwo>), Without<Ball>>,
 ) {
-    if timer.miss.tick(time.delta()).finished() {
-        for event in collision_events.iter() {
-            let mut closure = |ball: Entity, base: Entity| -> Option<()> {
-                ball_query.get(ball).ok()?;
-                let mut base = base_query.get_mut(base).ok()?;
-
-                let location = event.hit.location();
-
-                let lose = base.ball_count == 0;
-                if lose {
-                    game_over_events.send(GameOverEvent::Lose);
-                } else {
-                    base.ball_count -= 1;
-                }
-                timer.miss.reset();
-
-                player_miss_events.send(PlayerMissEvent {
-                    ball,
-                    location,
-                    lose,
+    for mut debounce in ball_query.iter_mut() {
+        debounce.miss.tick(clock.delta());
+    }
+
+    for event in collision_events.iter() {
+        let mut closure = |ball: Entity, base: Entity| -> Option<()> {
+            let mut debounce = ball_query.get_mut(ball).ok()?;
+            let (mut base, player_two) = base_query.get_mut(base).ok()?;
+            if !debounce.miss.finished() {
+                return Some(());
+            }
+
+            let location = event.hit.location();
+
+            let lose = base.ball_count == 0;
+            if lose {
+                game_over_events.send(match player_two {
+                    Some(_) => GameOverEvent::Win,
+                    None => GameOverEvent::Lose,
                 });
+            } else {
+                base.ball_count -= 1;
+            }
+            if player_two.is_none() {
+                score.ball_count = base.ball_count;
+            }
+            debounce.miss.reset();
 
-                Some(())
-            };
+            player_miss_events.send(PlayerMissEvent {
+                ball,
+                location,
+                lose,
+            });
 
-            closure(event.entities[0], event.entities[1])
-                .or_else(|| closure(event.entities[1], event.entities[0]));
-        }
+            Some(())
+        };
+
+        closure(event.entities[0], event.entities[1])
+            .or_else(|| closure(event.entities[1], event.entities[0]));
     }
 }
 
 /// Deals with [`GameOverEvent`].
 /// The system triggers a slow motion with the duration of [`GAME_OVER_SLOW_MOTION_DURATION`]
 fn game_over_slow_motion(
-    time: Res<Time>,
+    clock: Res<GameClock>,
     mut time_scale: ResMut<TimeScale>,
     mut game_over_events: EventReader<GameOverEvent>,
     mut game_over: Local<GameOver>,
@@ -750,18 +1009,18 @@ fn game_over_slow_motion(
         let mut target_time_scale = GAME_OVER_SLOW_MOTION_TIME_SCALE;
         let mut time_scale_damp = TIME_SCALE_DAMP;
 
-        if game_over.slow_motion_timer.tick(time.delta()).finished() {
+        if game_over.slow_motion_timer.tick(clock.delta()).finished() {
             target_time_scale = 1.0;
             time_scale_damp = GAME_OVER_TIME_SCALE_DAMP;
         }
 
         time_scale.0 = time_scale
             .0
-            .damp(target_time_scale, time_scale_damp, time.delta_seconds());
+            .damp(target_time_scale, time_scale_damp, clock.delta_seconds());
 
         if game_over
             .state_change_timer
-            .tick(time.delta())
+            .tick(clock.delta())
             .just_finished()
         {
             time_scale.reset();
@@ -777,31 +1036,35 @@ fn game_over_slow_motion(
 /// Emits [`BounceEvent`] when there the ball hits something after debouncing.
 #[allow(clippy::too_many_arguments)]
 fn ball_bounce(
-    time: Res<Time>,
-    mut timer: ResMut<Debounce>,
+    clock: Res<GameClock>,
     mut collision_events: EventReader<CollisionEvent>,
     mut bounce_events: EventWriter<BounceEvent>,
-    ball_query: Query<(), With<Ball>>,
+    mut ball_query: Query<&mut Debounce, With<Ball>>,
 ) {
-    if timer.bounce.tick(time.delta()).finished() {
-        for event in collision_events.iter() {
-            let mut closure = |ball: Entity, other: Entity| -> Option<()> {
-                ball_query.get(ball).ok()?;
-
-                let location = event.hit.location();
-                bounce_events.send(BounceEvent {
-                    ball,
-                    other,
-                    location,
-                });
+    for mut debounce in ball_query.iter_mut() {
+        debounce.bounce.tick(clock.delta());
+    }
 
-                timer.bounce.reset();
-                Some(())
-            };
+    for event in collision_events.iter() {
+        let mut closure = |ball: Entity, other: Entity| -> Option<()> {
+            let mut debounce = ball_query.get_mut(ball).ok()?;
+            if !debounce.bounce.finished() {
+                return Some(());
+            }
 
-            closure(event.entities[0], event.entities[1])
-                .or_else(|| closure(event.entities[1], event.entities[0]));
-        }
+            let location = event.hit.location();
+            bounce_events.send(BounceEvent {
+                ball,
+                other,
+                location,
+            });
+
+            debounce.bounce.reset();
+            Some(())
+        };
+
+        closure(event.entities[0], event.entities[1])
+            .or_else(|| closure(event.entities[1], event.entities[0]));
     }
 }
 
@@ -810,70 +1073,84 @@ fn ball_bounce(
 #[allow(clippy::too_many_arguments)]
 fn bounce_effects(
     mut commands: Commands,
-    time: Res<Time>,
-    mut timer: ResMut<Debounce>,
+    clock: Res<GameClock>,
     mut collision_events: EventReader<CollisionEvent>,
     mut camera_shake_events: EventWriter<CameraShakeEvent>,
-    mut bounce_entities: Local<Option<[Entity; 2]>>,
-    materials: Res<Materials>,
-    query: Query<(), With<Ball>>,
+    atlases: Res<AssetMap<TextureAtlas>>,
+    mut query: Query<&mut Debounce, With<Ball>>,
     motions: Query<Option<&Motion>>,
 ) {
-    if timer.effects.tick(time.delta()).finished() {
+    for mut debounce in query.iter_mut() {
+        debounce.effects.tick(clock.delta());
         if collision_events.is_empty() {
-            *bounce_entities = None;
+            debounce.effects_entities = None;
         }
+    }
 
-        for event in collision_events.iter() {
-            let results = event.entities.map(|entity| query.get(entity).is_ok());
-            if results.contains(&true) {
-                if bounce_entities.map_or(true, |entities| entities != event.entities) {
-                    let velocities = motions.many(event.entities).map(|maybe_motion| {
-                        maybe_motion.map_or(Vec2::ZERO, |motion| motion.velocity)
-                    });
-
-                    let velocity = if results[0] {
-                        velocities[0] - velocities[1]
-                    } else {
-                        velocities[1] - velocities[0]
-                    };
-
-                    let speed = velocity.length();
-                    let scale = (speed / MAX_BOUNCE_EFFECTS_SPEED).min(1.0);
-
-                    // screen shake
-                    let amplitude = velocity.normalize() * scale * 8.0;
-                    camera_shake_events.send(CameraShakeEvent { amplitude });
-                    timer.effects.reset();
-
-                    // hit effect
-                    commands.spawn((
-                        SpriteSheetBundle {
-                            transform: Transform {
-                                translation: event.hit.location().extend(0.0),
-                                rotation: Quat::from_rotation_z(
-                                    f32::atan2(-velocity.y, -velocity.x) + FRAC_PI_4,
-                                ),
-                                scale: Vec3::new(0.2, 0.2, 1.0),
-                            },
-                            texture_atlas: materials.hit.clone(),
-                            ..Default::default()
-                        },
-                        HitEffect::default(),
-                        Cleanup,
-                    ));
-                }
-
-                *bounce_entities = Some(event.entities);
-            }
+    for event in collision_events.iter() {
+        // the first ball of the two keeps the debouncing state
+        let Some(index) = event
+            .entities
+            .iter()
+            .position(|entity| query.contains(*entity))
+        else {
+            continue;
+        };
+        let Ok(mut debounce) = query.get_mut(event.entities[index]) else {
+            continue;
+        };
+        if !debounce.effects.finished() {
+            continue;
         }
+
+        if debounce
+            .effects_entities
+            .is_none_or(|entities| entities != event.entities)
+        {
+            let velocities = motions
+                .many(event.entities)
+                .map(|maybe_motion| maybe_motion.map_or(Vec2::ZERO, |motion| motion.velocity));
+
+            let velocity = if index == 0 {
+                velocities[0] - velocities[1]
+            } else {
+                velocities[1] - velocities[0]
+            };
+
+            let speed = velocity.length();
+            let scale = (speed / MAX_BOUNCE_EFFECTS_SPEED).min(1.0);
+
+            // screen shake
+            let amplitude = velocity.normalize() * scale * 8.0;
+            camera_shake_events.send(CameraShakeEvent { amplitude });
+            debounce.effects.reset();
+
+            // hit effect
+            commands.spawn((
+                SpriteSheetBundle {
+                    transform: Transform {
+                        translation: event.hit.location().extend(0.0),
+                        rotation: Quat::from_rotation_z(
+                            f32::atan2(-velocity.y, -velocity.x) + FRAC_PI_4,
+                        ),
+                        scale: Vec3::new(0.2, 0.2, 1.0),
+                    },
+                    texture_atlas: atlases.get("hit"),
+                    ..Default::default()
+                },
+                HitEffect::default(),
+                Cleanup,
+            ));
+        }
+
+        debounce.effects_entities = Some(event.entities);
     }
 }
 
 /// Creates full-screen explosion effects both when player lose or win.
 fn score_effects(
     mut commands: Commands,
-    materials: Res<Materials>,
+    images: Res<AssetMap<Image>>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut color_materials: ResMut<Assets<DeathEffectMaterial>>,
     mut player_miss_events: EventReader<PlayerMissEvent>,
@@ -889,7 +1166,7 @@ fn score_effects(
             commands.spawn((
                 MaterialMesh2dBundle {
                     mesh: meshes.add(shape::Quad::default().into()).into(),
-                    material: color_materials.add(materials.death.clone().into()),
+                    material: color_materials.add(images.get("death").into()),
                     transform: Transform::from_translation((location + offset).extend(0.9)),
                     ..Default::default()
                 },
@@ -934,26 +1211,33 @@ fn score_system(
 #[allow(clippy::too_many_arguments)]
 fn bounce_audio(
     audio: Res<AudioChannel<BounceAudioChannel>>,
-    audios: Res<Audios>,
+    audios: Res<AssetMap<AudioSource>>,
     volume: Res<AudioVolume>,
-    time: Res<Time>,
-    mut timer: ResMut<Debounce>,
+    clock: Res<GameClock>,
+    mut rng: ResMut<GameRng>,
     mut events: EventReader<CollisionEvent>,
-    mut bounce_entities: Local<Option<[Entity; 2]>>,
     query: Query<(Entity, &BounceAudio)>,
-    balls: Query<(), With<Ball>>,
+    mut balls: Query<&mut Debounce, With<Ball>>,
     motions: Query<Option<&Motion>>,
 ) {
-    let mut can_play_audio = timer.audio_bounce_long.tick(time.delta()).finished();
-    timer.audio_bounce_short.tick(time.delta());
-    timer.audio_hit.tick(time.delta());
+    for mut debounce in balls.iter_mut() {
+        debounce.audio_bounce_long.tick(clock.delta());
+        debounce.audio_bounce_short.tick(clock.delta());
+        debounce.audio_hit.tick(clock.delta());
+    }
 
     for event in events.iter() {
-        // one of the entities must be a ball
-        let results = event.entities.map(|entity| balls.get(entity).is_ok());
-        if !results.contains(&true) {
+        // one of the entities must be a ball, which keeps the debouncing state
+        let Some(ball) = event
+            .entities
+            .into_iter()
+            .find(|entity| balls.contains(*entity))
+        else {
             continue;
-        }
+        };
+        let Ok(mut debounce) = balls.get_mut(ball) else {
+            continue;
+        };
 
         let (entities, bounce_audio) = if let Ok(x) = query.get_many(event.entities) {
             let (entities, bounce_audios): (Vec<_>, Vec<_>) = x.iter().cloned().unzip();
@@ -967,20 +1251,18 @@ fn bounce_audio(
             continue;
         };
 
-        let (audio_source, debounce_timer) = match bounce_audio {
-            BounceAudio::Bounce => {
-                let index = fastrand::usize(..IMPACT_AUDIOS.len());
-                (
-                    audios.impact_audios[index].clone(),
-                    &timer.audio_bounce_short,
-                )
-            }
-            BounceAudio::Hit => (audios.hit_audio.clone(), &timer.audio_hit),
+        let (audio_source, debounced) = match bounce_audio {
+            BounceAudio::Bounce => (
+                audios.pick("impact", rng.cosmetic()),
+                debounce.audio_bounce_short.finished(),
+            ),
+            BounceAudio::Hit => (audios.get("hit"), debounce.audio_hit.finished()),
         };
 
-        if entities != *bounce_entities {
-            can_play_audio = debounce_timer.finished();
-            *bounce_entities = entities;
+        let mut can_play_audio = debounce.audio_bounce_long.finished();
+        if entities != debounce.audio_entities {
+            can_play_audio = debounced;
+            debounce.audio_entities = entities;
         }
 
         if can_play_audio {
@@ -995,15 +1277,15 @@ fn bounce_audio(
 
                 let panning = event.hit.location().x / ARENA_WIDTH + 0.5;
                 let volume = volume.effects * (0.5 * normalized_speed + 0.5);
-                let playback_rate = 0.4 * fastrand::f32() + 0.8;
+                let playback_rate = 0.4 * rng.cosmetic().f32() + 0.8;
                 audio
                     .play(audio_source)
                     .with_volume(volume.into())
                     .with_panning(panning.into())
                     .with_playback_rate(playback_rate.into());
 
-                timer.audio_bounce_long.reset();
-                timer.audio_bounce_short.reset();
+                debounce.audio_bounce_long.reset();
+                debounce.audio_bounce_short.reset();
             }
         }
     }
@@ -1011,7 +1293,7 @@ fn bounce_audio(
 
 fn score_audio(
     audio: Res<AudioChannel<ScoreAudioChannel>>,
-    audios: Res<Audios>,
+    audios: Res<AssetMap<AudioSource>>,
     volume: Res<AudioVolume>,
     mut player_miss_events: EventReader<PlayerMissEvent>,
     mut game_over_events: EventReader<GameOverEvent>,
@@ -1019,15 +1301,15 @@ fn score_audio(
     for event in player_miss_events.iter() {
         let panning = event.location.x / ARENA_WIDTH + 0.5;
         audio
-            .play(audios.miss_audio.clone())
+            .play(audios.get("miss"))
             .with_volume(volume.effects.into())
             .with_panning(panning.into());
     }
 
     for event in game_over_events.iter() {
         let audio_source = match event {
-            GameOverEvent::Win => audios.explosion_audio.clone(),
-            GameOverEvent::Lose => audios.lose_audio.clone(),
+            GameOverEvent::Win => audios.get("explosion"),
+            GameOverEvent::Lose => audios.get("lose"),
         };
         audio.play(audio_source).with_volume(volume.effects.into());
     }
@@ -1,21 +1,31 @@
 use crate::{
-    constants::{PHYSICS_REST_SPEED, PHYSICS_TIME_STEP},
+    config::PhysicsConfig,
+    constants::{PHYSICS_MAX_TIME_OF_IMPACTS, PHYSICS_TIME_STEP},
     utils::*,
     TimeScale,
 };
-use bevy::{prelude::*, render::view::RenderLayers, time::FixedTimestep};
+use bevy::{ecs::system::SystemParam, prelude::*, render::view::RenderLayers, utils::HashMap};
+use itertools::Itertools;
 
 pub struct PhysicsPlugin;
 
 impl Plugin for PhysicsPlugin {
     fn build(&self, app: &mut App) {
         let systems = SystemSet::new()
-            .with_run_criteria(FixedTimestep::step(PHYSICS_TIME_STEP as f64))
+            .with_run_criteria(fixed_step("physics", PHYSICS_TIME_STEP))
             .with_system(init_motion)
             .with_system(movement)
-            .with_system(collision.after(init_motion).after(movement));
+            .with_system(collision.after(init_motion).after(movement))
+            .with_system(sensor.after(collision));
 
-        app.add_event::<CollisionEvent>()
+        app.init_resource::<BroadPhase>()
+            .init_resource::<PhysicsConfig>()
+            .init_resource::<MaxTimeOfImpacts>()
+            .init_resource::<FixedSteps>()
+            .add_event::<CollisionEvent>()
+            .add_event::<TriggerEnter>()
+            .add_event::<TriggerStay>()
+            .add_event::<TriggerExit>()
             .add_system_set_to_stage(CoreStage::PostUpdate, systems);
     }
 }
@@ -52,14 +62,14 @@ impl PhysicsLayers {
 
 #[derive(Component)]
 pub struct RigidBody {
-    pub size: Vec2,
+    pub shape: Shape,
     pub inverted_mass: f32,
     pub bounciness: f32,
     pub friction: f32,
 }
 
 impl RigidBody {
-    pub fn new(size: Vec2, mass: f32, bounciness: f32, friction: f32) -> Self {
+    pub fn new(shape: Shape, mass: f32, bounciness: f32, friction: f32) -> Self {
         let inverted_mass = if mass < f32::EPSILON {
             0.0
         } else {
@@ -67,7 +77,7 @@ impl RigidBody {
         };
 
         Self {
-            size,
+            shape,
             inverted_mass,
             bounciness,
             friction,
@@ -79,20 +89,96 @@ impl RigidBody {
     }
 }
 
+/// Marks a body that detects overlaps without colliding, see [`TriggerEnter`], [`TriggerStay`] and [`TriggerExit`].
+#[derive(Default, Component)]
+pub struct Sensor;
+
+/// Sent when a body starts overlapping a [`Sensor`].
+#[derive(Debug, Clone)]
+pub struct TriggerEnter {
+    pub sensor: Entity,
+    pub other: Entity,
+    pub location: Vec2,
+}
+
+/// Sent on every physics step while a body keeps overlapping a [`Sensor`].
+#[derive(Debug, Clone)]
+pub struct TriggerStay {
+    pub sensor: Entity,
+    pub other: Entity,
+    pub location: Vec2,
+}
+
+/// Sent when a body stops overlapping a [`Sensor`], with the location of the last contact.
+#[derive(Debug, Clone)]
+pub struct TriggerExit {
+    pub sensor: Entity,
+    pub other: Entity,
+    pub location: Vec2,
+}
+
+/// How [`collision`] picks the pairs of bodies to test.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Resource)]
+pub enum BroadPhase {
+    /// Sorts swept bounding boxes along the x axis and only tests overlapping ones.
+    #[default]
+    SweepAndPrune,
+    /// Tests every pair of bodies.
+    BruteForce,
+}
+
+/// Caps how many times of impact [`collision`] resolves in order within one step.
+/// Contacts beyond the cap are resolved pair by pair.
+#[derive(Debug, Clone, Copy, Resource)]
+pub struct MaxTimeOfImpacts(pub usize);
+
+impl Default for MaxTimeOfImpacts {
+    fn default() -> Self {
+        Self(PHYSICS_MAX_TIME_OF_IMPACTS)
+    }
+}
+
+struct BroadPhaseBody {
+    entity: Entity,
+    collider: Collider,
+    velocity: Vec2,
+    min: Vec2,
+    max: Vec2,
+    layers: RenderLayers,
+}
+
 #[derive(Default, Component)]
 pub struct Motion {
     pub velocity: Vec2,
     pub translation: Vec3,
 }
 
+/// Velocity changes and hit are given for each entity in order, with the hit normal pointing at the first one.
 pub struct CollisionEvent {
     pub entities: [Entity; 2],
     pub delta_velocities: [Vec2; 2],
-    pub bounciness: f32,
-    pub friction: f32,
     pub hit: Hit,
 }
 
+impl CollisionEvent {
+    fn new(entities: [Entity; 2], delta_velocities: [Vec2; 2], hit: Hit) -> Self {
+        // sort the entities, keeping the rest relative to them
+        let [e1, e2] = entities;
+        let [v1, v2] = delta_velocities;
+        let (entities, delta_velocities, hit) = if e1 <= e2 {
+            ([e1, e2], [v1, v2], hit)
+        } else {
+            ([e2, e1], [v2, v1], hit.flipped())
+        };
+
+        Self {
+            entities,
+            delta_velocities,
+            hit,
+        }
+    }
+}
+
 fn init_motion(mut query: Query<(&Transform, &mut Motion), Added<Motion>>) {
     for (transform, mut motion) in query.iter_mut() {
         motion.translation = transform.translation;
@@ -108,23 +194,302 @@ fn movement(time_scale: Res<TimeScale>, mut query: Query<(&mut Motion, &mut Tran
     }
 }
 
+/// Finds the pairs of bodies that may collide during this step, as indices into `bodies`.
+/// Pairs are ordered as [`Query::iter_combinations`] would visit them.
+fn broad_phase(broad_phase: BroadPhase, bodies: &[BroadPhaseBody]) -> Vec<(usize, usize)> {
+    let mut pairs = vec![];
+
+    match broad_phase {
+        BroadPhase::BruteForce => {
+            for (i, a) in bodies.iter().enumerate() {
+                for (j, b) in bodies.iter().enumerate().skip(i + 1) {
+                    if a.layers.intersects(&b.layers) {
+                        pairs.push((i, j));
+                    }
+                }
+            }
+        }
+        BroadPhase::SweepAndPrune => {
+            let mut order = (0..bodies.len()).collect_vec();
+            order.sort_by(|&i, &j| bodies[i].min.x.total_cmp(&bodies[j].min.x));
+
+            let mut active: Vec<usize> = vec![];
+            for i in order {
+                let a = &bodies[i];
+                active.retain(|&j| bodies[j].max.x >= a.min.x);
+
+                for &j in &active {
+                    let b = &bodies[j];
+                    if a.min.y <= b.max.y && a.max.y >= b.min.y && a.layers.intersects(&b.layers) {
+                        pairs.push((i.min(j), i.max(j)));
+                    }
+                }
+                active.push(i);
+            }
+            pairs.sort_unstable();
+        }
+    }
+
+    pairs
+}
+
+/// Combined material of two bodies in contact.
+pub struct Contact {
+    bounciness: f32,
+    friction: f32,
+    impulse: f32,
+    /// See [`PhysicsConfig::rest_speed`].
+    rest_speed: f32,
+}
+
+impl Contact {
+    pub fn new(
+        rb1: &RigidBody,
+        pl1: &PhysicsLayers,
+        rb2: &RigidBody,
+        pl2: &PhysicsLayers,
+        rest_speed: f32,
+    ) -> Self {
+        let bounciness = if pl1.bounciness.intersects(&pl2.bounciness) {
+            (rb1.bounciness * rb2.bounciness).sqrt()
+        } else {
+            0.0
+        };
+        let friction = if pl1.friction.intersects(&pl2.friction) {
+            (rb1.friction * rb2.friction).sqrt()
+        } else {
+            0.0
+        };
+        let impulse = (rb1.inverted_mass + rb2.inverted_mass).recip();
+
+        Self {
+            bounciness,
+            friction,
+            impulse,
+            rest_speed,
+        }
+    }
+
+    /// Velocity change of a body hit along `normal`, given the velocity of the other body relative to it.
+    /// Returns [`None`] if the bodies are moving apart.
+    pub fn resolve(&self, rigid_body: &RigidBody, velocity: Vec2, normal: Vec2) -> Option<Impulse> {
+        let normal_speed = velocity.dot(normal);
+
+        // do not process if objects are moving apart
+        if normal_speed < 0.0 {
+            return None;
+        }
+
+        let tan = (velocity - normal_speed * normal).normalize_or_zero();
+        let tan_speed = velocity.dot(tan);
+
+        let bounciness = if normal_speed < self.rest_speed {
+            0.0
+        } else {
+            self.bounciness
+        };
+
+        let normal_impulse = (1.0 + bounciness) * self.impulse * normal_speed;
+        let tan_impulse = (self.impulse * tan_speed).min(self.friction * normal_impulse);
+
+        let normal_delta = normal_impulse * rigid_body.inverted_mass;
+        let tan_delta = tan_impulse * rigid_body.inverted_mass;
+
+        Some(Impulse {
+            delta_velocity: normal_delta * normal + tan_delta * tan,
+            normal_delta,
+            bounciness,
+            share: self.impulse * rigid_body.inverted_mass,
+        })
+    }
+}
+
+pub struct Impulse {
+    pub delta_velocity: Vec2,
+    normal_delta: f32,
+    bounciness: f32,
+    /// Share of the penetration this body has to move back, based on masses.
+    share: f32,
+}
+
+impl Impulse {
+    /// Corrects the position of a body that moved from `previous` to `position` and got this impulse.
+    pub fn correct(
+        &self,
+        hit: &Hit,
+        previous: Vec3,
+        position: Vec3,
+        normal: Vec2,
+        delta_time: f32,
+    ) -> Vec3 {
+        match hit {
+            Hit::Penetration(x) => {
+                let delta = (previous - position).truncate().dot(normal);
+                let depth = x.depth.abs();
+
+                // compensate penetration based on masses
+                let correction = depth * self.share;
+                // compensate normal impulse to be applied in the next physics update
+                let debounce = (1.0 - self.bounciness) * self.normal_delta * delta_time;
+                let normal_delta = delta + correction - debounce;
+                position + normal_delta * normal.extend(0.0)
+            }
+            Hit::Cast(x) => {
+                if x.near_time > 0.0 {
+                    previous.lerp(position, x.near_time)
+                } else {
+                    position
+                }
+            }
+        }
+    }
+}
+
 #[allow(clippy::type_complexity)]
 fn collision(
     time_scale: Res<TimeScale>,
-    mut query: Query<(
-        Entity,
-        &RigidBody,
-        &mut Transform,
-        Option<&mut Motion>,
-        &PhysicsLayers,
-    )>,
+    broad_phase_mode: Res<BroadPhase>,
+    max_time_of_impacts: Res<MaxTimeOfImpacts>,
+    physics: Res<PhysicsConfig>,
+    mut query: Query<
+        (
+            Entity,
+            &RigidBody,
+            &mut Transform,
+            Option<&mut Motion>,
+            &PhysicsLayers,
+        ),
+        Without<Sensor>,
+    >,
     mut events: EventWriter<CollisionEvent>,
 ) {
     let delta_time = PHYSICS_TIME_STEP * time_scale.0;
-    let mut combinations = query.iter_combinations_mut();
-    while let Some([(e1, rb1, t1, m1, pl1), (e2, rb2, t2, m2, pl2)]) = combinations.fetch_next() {
-        if !pl1.collision.intersects(&pl2.collision) {
+
+    // swept boxes, padded by the body size to cover corrections made earlier in this step
+    let broad_phase_bodies = |query: &Query<
+        (
+            Entity,
+            &RigidBody,
+            &mut Transform,
+            Option<&mut Motion>,
+            &PhysicsLayers,
+        ),
+        Without<Sensor>,
+    >| {
+        query
+            .iter()
+            .map(|(entity, rigid_body, transform, motion, layers)| {
+                let position = transform.translation.truncate();
+                let previous_position =
+                    motion.map_or(position, |motion| motion.translation.truncate());
+                let extent = 1.5 * rigid_body.shape.size();
+                BroadPhaseBody {
+                    entity,
+                    collider: Collider {
+                        previous_position,
+                        position,
+                        shape: rigid_body.shape,
+                    },
+                    velocity: motion.map_or(Vec2::ZERO, |motion| motion.velocity),
+                    min: position.min(previous_position) - extent,
+                    max: position.max(previous_position) + extent,
+                    layers: layers.collision,
+                }
+            })
+            .collect_vec()
+    };
+
+    // time of impact solver: resolve the earliest contact, advance to it, and repeat
+    let mut time_left = delta_time;
+    let mut resolved = vec![];
+    for _ in 0..max_time_of_impacts.0 {
+        let bodies = broad_phase_bodies(&query);
+        let Some((i, j, cast)) = broad_phase(*broad_phase_mode, &bodies)
+            .into_iter()
+            .filter_map(|(i, j)| {
+                let (a, b) = (&bodies[i], &bodies[j]);
+                let x = sweep(&a.collider, &b.collider)?;
+                // skip contacts that were already touching, grazing, or separating
+                (x.near_time >= 0.0
+                    && x.near_time < x.far_time
+                    && (b.velocity - a.velocity).dot(x.normal) > 0.0)
+                    .then_some((i, j, x))
+            })
+            .min_by(|(_, _, a), (_, _, b)| a.near_time.total_cmp(&b.near_time))
+        else {
+            break;
+        };
+
+        // advance every body to the time of impact
+        for (_, _, transform, motion, _) in query.iter_mut() {
+            if let Some(mut motion) = motion {
+                motion.translation = motion
+                    .translation
+                    .lerp(transform.translation, cast.near_time);
+            }
+        }
+        time_left *= 1.0 - cast.near_time;
+
+        let Ok([(e1, rb1, t1, m1, pl1), (e2, rb2, t2, m2, pl2)]) =
+            query.get_many_mut([bodies[i].entity, bodies[j].entity])
+        else {
+            break;
+        };
+
+        let contact = Contact::new(rb1, pl1, rb2, pl2, physics.rest_speed);
+        let (v1, v2) = (bodies[i].velocity, bodies[j].velocity);
+        let normal = cast.normal;
+
+        // bounce, then move on with the new velocity for the rest of the step
+        let resolve = |rigid_body: &RigidBody,
+                       mut motion: Mut<Motion>,
+                       mut transform: Mut<Transform>,
+                       velocity: Vec2,
+                       normal: Vec2|
+         -> Option<Vec2> {
+            let impulse = contact.resolve(rigid_body, velocity, normal)?;
+            motion.velocity += impulse.delta_velocity;
+            transform.translation = motion.translation + (motion.velocity * time_left).extend(0.0);
+            Some(impulse.delta_velocity)
+        };
+
+        let mut delta_velocities: [Vec2; 2] = Default::default();
+        if let Some(motion) = m1 {
+            delta_velocities[0] = resolve(rb1, motion, t1, v2 - v1, normal).unwrap_or_default();
+        }
+        if let Some(motion) = m2 {
+            delta_velocities[1] = resolve(rb2, motion, t2, v1 - v2, -normal).unwrap_or_default();
+        }
+
+        let event = CollisionEvent::new([e1, e2], delta_velocities, Hit::Cast(cast));
+        resolved.push(event.entities);
+        events.send(event);
+    }
+
+    // resolve overlaps, and contacts left over by the solver, pair by pair
+    let bodies = broad_phase_bodies(&query);
+    for (i, j) in broad_phase(*broad_phase_mode, &bodies) {
+        let Ok([(e1, rb1, t1, m1, pl1), (e2, rb2, t2, m2, pl2)]) =
+            query.get_many_mut([bodies[i].entity, bodies[j].entity])
+        else {
             continue;
+        };
+
+        // a pair the solver resolved is done with, unless other contacts pushed it back into overlap
+        let mut entities = [e1, e2];
+        entities.sort();
+        if resolved.contains(&entities) {
+            let still = |transform: &Transform, rigid_body: &RigidBody| {
+                let position = transform.translation.truncate();
+                Collider {
+                    previous_position: position,
+                    position,
+                    shape: rigid_body.shape,
+                }
+            };
+            if collide(&still(&t1, rb1), &still(&t2, rb2)).is_none() {
+                continue;
+            }
         }
 
         let (p1, v1) = match &m1 {
@@ -140,25 +505,15 @@ fn collision(
             &Collider {
                 previous_position: p1.truncate(),
                 position: t1.translation.truncate(),
-                size: rb1.size,
+                shape: rb1.shape,
             },
             &Collider {
                 previous_position: p2.truncate(),
                 position: t2.translation.truncate(),
-                size: rb2.size,
+                shape: rb2.shape,
             },
         ) {
-            let bounciness = if pl1.bounciness.intersects(&pl2.bounciness) {
-                (rb1.bounciness * rb2.bounciness).sqrt()
-            } else {
-                0.0
-            };
-            let friction = if pl1.friction.intersects(&pl2.friction) {
-                (rb1.friction * rb2.friction).sqrt()
-            } else {
-                0.0
-            };
-            let impulse = (rb1.inverted_mass + rb2.inverted_mass).recip();
+            let contact = Contact::new(rb1, pl1, rb2, pl2, physics.rest_speed);
             let normal = hit.normal();
 
             let resolve = |rigid_body: &RigidBody,
@@ -167,54 +522,17 @@ fn collision(
                            velocity: Vec2,
                            normal: Vec2|
              -> Option<Vec2> {
-                let normal_speed = velocity.dot(normal);
-
-                // do not process if objects are moving apart
-                if normal_speed < 0.0 {
-                    return None;
-                }
+                let impulse = contact.resolve(rigid_body, velocity, normal)?;
+                motion.velocity += impulse.delta_velocity;
+                transform.translation = impulse.correct(
+                    &hit,
+                    motion.translation,
+                    transform.translation,
+                    normal,
+                    delta_time,
+                );
 
-                let tan = (velocity - normal_speed * normal).normalize_or_zero();
-                let tan_speed = velocity.dot(tan);
-
-                let bounciness = if normal_speed < PHYSICS_REST_SPEED {
-                    0.0
-                } else {
-                    bounciness
-                };
-
-                let normal_impulse = (1.0 + bounciness) * impulse * normal_speed;
-                let tan_impulse = (impulse * tan_speed).min(friction * normal_impulse);
-
-                let normal_delta = normal_impulse * rigid_body.inverted_mass;
-                let tan_delta = tan_impulse * rigid_body.inverted_mass;
-                let delta_velocity = normal_delta * normal + tan_delta * tan;
-                motion.velocity += delta_velocity;
-
-                // translation correction
-                match &hit {
-                    Hit::Penetration(x) => {
-                        let delta = (motion.translation - transform.translation)
-                            .truncate()
-                            .dot(normal);
-                        let depth = x.depth.abs();
-
-                        // compensate penetration based on masses
-                        let correction = depth * impulse * rigid_body.inverted_mass;
-                        // compensate normal impulse to be applied in the next physics update
-                        let debounce = (1.0 - bounciness) * normal_delta * delta_time;
-                        let normal_delta = delta + correction - debounce;
-                        transform.translation += normal_delta * normal.extend(0.0);
-                    }
-                    Hit::Cast(x) => {
-                        if x.near_time > 0.0 {
-                            transform.translation =
-                                motion.translation.lerp(transform.translation, x.near_time);
-                        }
-                    }
-                };
-
-                Some(delta_velocity)
+                Some(impulse.delta_velocity)
             };
 
             let mut delta_velocities: [Vec2; 2] = Default::default();
@@ -226,15 +544,296 @@ fn collision(
                     resolve(rb2, motion, t2, v1 - v2, -normal).unwrap_or_default();
             }
 
-            let mut entities = [e1, e2];
-            entities.sort();
-            events.send(CollisionEvent {
-                entities,
-                delta_velocities,
-                bounciness,
-                friction,
-                hit,
+            events.send(CollisionEvent::new([e1, e2], delta_velocities, hit));
+        }
+    }
+}
+
+/// Detects bodies overlapping [`Sensor`]s during this step.
+#[allow(clippy::type_complexity)]
+fn sensor(
+    mut overlaps: Local<HashMap<[Entity; 2], Vec2>>,
+    sensors: Query<
+        (
+            Entity,
+            &RigidBody,
+            &Transform,
+            Option<&Motion>,
+            &PhysicsLayers,
+        ),
+        With<Sensor>,
+    >,
+    bodies: Query<(
+        Entity,
+        &RigidBody,
+        &Transform,
+        Option<&Motion>,
+        &PhysicsLayers,
+    )>,
+    mut enter_events: EventWriter<TriggerEnter>,
+    mut stay_events: EventWriter<TriggerStay>,
+    mut exit_events: EventWriter<TriggerExit>,
+) {
+    let collider = |rigid_body: &RigidBody, transform: &Transform, motion: Option<&Motion>| {
+        let position = transform.translation.truncate();
+        Collider {
+            previous_position: motion.map_or(position, |motion| motion.translation.truncate()),
+            position,
+            shape: rigid_body.shape,
+        }
+    };
+
+    let mut current = HashMap::default();
+    for (sensor, rb1, t1, m1, pl1) in sensors.iter() {
+        let a = collider(rb1, t1, m1);
+        for (other, rb2, t2, m2, pl2) in bodies.iter() {
+            if other == sensor || !pl1.collision.intersects(&pl2.collision) {
+                continue;
+            }
+
+            // a cast starting before this step means the body is leaving
+            let hit = collide(&a, &collider(rb2, t2, m2))
+                .filter(|hit| !matches!(hit, Hit::Cast(x) if x.near_time < 0.0));
+            if let Some(hit) = hit {
+                let location = hit.location();
+                if overlaps.contains_key(&[sensor, other]) {
+                    stay_events.send(TriggerStay {
+                        sensor,
+                        other,
+                        location,
+                    });
+                } else {
+                    enter_events.send(TriggerEnter {
+                        sensor,
+                        other,
+                        location,
+                    });
+                }
+                current.insert([sensor, other], location);
+            }
+        }
+    }
+
+    for (&[sensor, other], &location) in overlaps.iter() {
+        if !current.contains_key(&[sensor, other]) {
+            exit_events.send(TriggerExit {
+                sensor,
+                other,
+                location,
             });
         }
     }
+
+    *overlaps = current;
+}
+
+/// A body found by [`PhysicsQuery`].
+#[derive(Debug, Clone)]
+pub struct QueryHit {
+    pub entity: Entity,
+    pub location: Vec2,
+    /// Surface normal of the body, facing the query.
+    pub normal: Vec2,
+    /// Fraction of the cast travelled before the hit; zero for overlaps.
+    pub time: f32,
+}
+
+/// Asks the physics world about the bodies at their current positions. [`Sensor`]s are not included.
+#[derive(SystemParam)]
+pub struct PhysicsQuery<'w, 's> {
+    bodies: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static RigidBody,
+            &'static Transform,
+            &'static PhysicsLayers,
+        ),
+        Without<Sensor>,
+    >,
+}
+
+impl<'w, 's> PhysicsQuery<'w, 's> {
+    fn colliders(&self, mask: RenderLayers) -> impl Iterator<Item = (Entity, Collider)> + '_ {
+        self.bodies
+            .iter()
+            .filter(move |(_, _, _, layers)| layers.collision.intersects(&mask))
+            .map(|(entity, rigid_body, transform, _)| {
+                let position = transform.translation.truncate();
+                let collider = Collider {
+                    previous_position: position,
+                    position,
+                    shape: rigid_body.shape,
+                };
+                (entity, collider)
+            })
+    }
+
+    /// First body hit by a ray from `origin` to `origin + delta`. Bodies containing `origin` are ignored.
+    pub fn raycast(&self, origin: Vec2, delta: Vec2, mask: RenderLayers) -> Option<QueryHit> {
+        self.shape_cast(Shape::Circle(0.0), origin, delta, mask)
+    }
+
+    /// First body hit by `shape` moving from `origin` to `origin + delta`.
+    /// Bodies already overlapping the shape at `origin` are ignored.
+    pub fn shape_cast(
+        &self,
+        shape: Shape,
+        origin: Vec2,
+        delta: Vec2,
+        mask: RenderLayers,
+    ) -> Option<QueryHit> {
+        let caster = Collider {
+            previous_position: origin,
+            position: origin + delta,
+            shape,
+        };
+
+        self.colliders(mask)
+            .filter_map(|(entity, collider)| {
+                let x = cast_shape(&caster, &collider)?;
+                (x.near_time >= 0.0).then_some(QueryHit {
+                    entity,
+                    location: x.location,
+                    normal: x.normal,
+                    time: x.near_time,
+                })
+            })
+            .min_by(|a, b| a.time.total_cmp(&b.time))
+    }
+
+    /// All bodies overlapping an axis-aligned box.
+    pub fn overlap_box(&self, center: Vec2, size: Vec2, mask: RenderLayers) -> Vec<QueryHit> {
+        let area = Collider {
+            previous_position: center,
+            position: center,
+            shape: Shape::Box(size),
+        };
+
+        self.colliders(mask)
+            .filter_map(|(entity, collider)| {
+                let x = overlap(&area, &collider)?;
+                Some(QueryHit {
+                    entity,
+                    location: x.location,
+                    normal: x.normal,
+                    time: 0.0,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Distance the ball travels in one step, enough to hit the paddle and then the wall.
+    const TRAVEL: f32 = 120.0;
+
+    struct Outcome {
+        translation: Vec3,
+        velocity: Vec2,
+        hits: usize,
+    }
+
+    /// Runs one step of a ball at the origin, moving right towards a paddle at x = 40 with a wall at x = -40.
+    /// The bodies are spawned in `order`: 0 for the ball, 1 for the paddle and 2 for the wall.
+    fn ball_between(order: [usize; 3], max_time_of_impacts: usize) -> Outcome {
+        let mut world = World::new();
+        world.init_resource::<TimeScale>();
+        world.init_resource::<BroadPhase>();
+        world.init_resource::<PhysicsConfig>();
+        world.insert_resource(MaxTimeOfImpacts(max_time_of_impacts));
+        world.init_resource::<Events<CollisionEvent>>();
+
+        let mut ball = None;
+        for body in order {
+            match body {
+                0 => {
+                    ball = Some(
+                        world
+                            .spawn((
+                                RigidBody::new(Shape::Circle(5.0), 1.0, 1.0, 0.0),
+                                Transform::default(),
+                                Motion {
+                                    velocity: Vec2::X * TRAVEL / PHYSICS_TIME_STEP,
+                                    ..Default::default()
+                                },
+                                PhysicsLayers::BALL,
+                            ))
+                            .id(),
+                    )
+                }
+                1 => {
+                    world.spawn((
+                        RigidBody::new(Shape::Box(Vec2::new(10.0, 200.0)), 0.0, 1.0, 0.0),
+                        Transform::from_xyz(40.0, 0.0, 0.0),
+                        PhysicsLayers::PLAYER,
+                    ));
+                }
+                _ => {
+                    world.spawn((
+                        RigidBody::new(Shape::Box(Vec2::new(10.0, 200.0)), 0.0, 1.0, 0.0),
+                        Transform::from_xyz(-40.0, 0.0, 0.0),
+                        PhysicsLayers::BOUNDARY,
+                    ));
+                }
+            }
+        }
+
+        let mut stage = SystemStage::single_threaded()
+            .with_system(movement)
+            .with_system(collision.after(movement));
+        stage.run(&mut world);
+
+        let ball = ball.unwrap();
+        let hits = world
+            .resource::<Events<CollisionEvent>>()
+            .iter_current_update_events()
+            .filter(|event| event.entities.contains(&ball))
+            .count();
+        let entity = world.entity(ball);
+        Outcome {
+            translation: entity.get::<Transform>().unwrap().translation,
+            velocity: entity.get::<Motion>().unwrap().velocity,
+            hits,
+        }
+    }
+
+    fn assert_near(a: f32, b: f32) {
+        assert!((a - b).abs() < 1e-3, "{a} != {b}");
+    }
+
+    #[test]
+    fn ball_hits_paddle_and_wall_in_one_step() {
+        let outcome = ball_between([0, 1, 2], PHYSICS_MAX_TIME_OF_IMPACTS);
+
+        // 30 to the paddle, 60 back to the wall, and the last 30 towards the paddle again
+        assert_eq!(outcome.hits, 2);
+        assert_near(outcome.translation.x, 0.0);
+        assert_near(outcome.velocity.x, TRAVEL / PHYSICS_TIME_STEP);
+    }
+
+    #[test]
+    fn order_of_bodies_does_not_matter() {
+        let expected = ball_between([0, 1, 2], PHYSICS_MAX_TIME_OF_IMPACTS);
+        for order in [[0, 2, 1], [1, 0, 2], [1, 2, 0], [2, 0, 1], [2, 1, 0]] {
+            let outcome = ball_between(order, PHYSICS_MAX_TIME_OF_IMPACTS);
+            assert_eq!(outcome.hits, expected.hits, "{order:?}");
+            assert_near(outcome.translation.x, expected.translation.x);
+            assert_near(outcome.velocity.x, expected.velocity.x);
+        }
+    }
+
+    #[test]
+    fn capped_solver_leaves_the_wall_to_the_cleanup_pass() {
+        let outcome = ball_between([0, 1, 2], 1);
+
+        // the solver only bounces off the paddle, and the cleanup pass stops the ball at the wall
+        assert_eq!(outcome.hits, 2);
+        assert_near(outcome.translation.x, -30.0);
+        assert!(outcome.velocity.x > 0.0, "{}", outcome.velocity.x);
+    }
 }
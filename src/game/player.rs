@@ -1,10 +1,19 @@
 use super::{
+    autopilot::Autopilot,
     ball::{Ball, Point, Trajectory},
+    base::PlayerBase,
     enemy::Controller,
     physics::{CollisionEvent, Motion},
 };
-use crate::{constants::*, utils::Damp, TimeScale};
+use crate::{
+    config::GameConfig,
+    constants::*,
+    input::{ControlInput, PlayerTwoInput},
+    utils::{Damp, GameClock},
+    TimeScale,
+};
 use bevy::{input::mouse::MouseMotion, prelude::*};
+use serde::{Deserialize, Serialize};
 use std::{ops::Add, time::Duration};
 
 #[derive(Component)]
@@ -16,14 +25,19 @@ pub struct Player {
 
 impl Default for Player {
     fn default() -> Self {
+        let config = GameConfig::current().player;
         Self {
-            max_speed: PLAYER_MAX_SPEED,
-            sensitivity: PLAYER_SENSITIVITY,
-            damp: PLAYER_DAMP,
+            max_speed: config.max_speed,
+            sensitivity: config.sensitivity,
+            damp: config.damp,
         }
     }
 }
 
+/// Marks the paddle and the base of the second player in versus, at the top of the arena.
+#[derive(Component)]
+pub struct PlayerTwo;
+
 #[derive(Component)]
 pub struct MotionOverride {
     pub timer: Timer,
@@ -41,6 +55,19 @@ impl Default for MotionOverride {
     }
 }
 
+/// Whether [`PlayerAssist`] helps the paddle and slows the time down, chosen in the settings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Resource, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Assist {
+    pub enabled: bool,
+}
+
+impl Default for Assist {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
 #[derive(Component)]
 pub struct PlayerAssist {
     pub range: f32,
@@ -51,32 +78,63 @@ pub struct PlayerAssist {
 
 impl Default for PlayerAssist {
     fn default() -> Self {
+        let config = GameConfig::current().assist;
         Self {
-            range: PLAYER_ASSIST_RANGE,
-            speed: PLAYER_ASSIST_SPEED,
-            vertical_speed_threshold: PLAYER_ASSIST_VERTICAL_SPEED_THRESHOLD,
-            speed_threshold: PLAYER_ASSIST_SPEED_THRESHOLD,
+            range: config.range,
+            speed: config.speed,
+            vertical_speed_threshold: config.vertical_speed_threshold,
+            speed_threshold: config.speed_threshold,
         }
     }
 }
 
-pub fn move_player(
+/// Paddle movement requested during the current frame.
+#[derive(Default, Resource)]
+pub struct PlayerInput {
+    /// Movement in mouse units; gamepad and keyboard directions are converted to it,
+    /// so that [`Player::sensitivity`] applies to all devices alike.
+    pub delta: Vec2,
+    /// Movement of the [`PlayerTwo`] paddle in versus, in the same units.
+    pub delta_two: Vec2,
+}
+
+pub fn read_player_input(
     time: Res<Time>,
-    time_scale: Res<TimeScale>,
+    controls: Res<ControlInput>,
+    player_two: Res<PlayerTwoInput>,
     mut mouse_motion_events: EventReader<MouseMotion>,
-    mut query: Query<(&Player, &Controller, &mut MotionOverride, &mut Motion)>,
+    mut input: ResMut<PlayerInput>,
 ) {
-    let delta = mouse_motion_events
+    input.delta = mouse_motion_events
         .iter()
         .map(|mouse_motion| mouse_motion.delta)
         .map(|v| Vec2::new(v.x, -v.y))
-        .fold(Vec2::ZERO, Vec2::add);
+        .fold(Vec2::ZERO, Vec2::add)
+        + controls.direction * CONTROL_SPEED * time.delta_seconds();
+    input.delta_two = player_two.direction * CONTROL_SPEED * time.delta_seconds();
+}
 
-    let delta_seconds = time.delta_seconds() * time_scale.0;
+pub fn move_player(
+    clock: Res<GameClock>,
+    input: Res<PlayerInput>,
+    mut query: Query<(
+        &Player,
+        &Controller,
+        &mut MotionOverride,
+        &mut Motion,
+        Option<&PlayerTwo>,
+    )>,
+) {
+    let delta_seconds = clock.scaled_delta_seconds();
 
-    for (player, controller, mut motion_override, mut motion) in query.iter_mut() {
+    for (player, controller, mut motion_override, mut motion, player_two) in query.iter_mut() {
+        let delta = if player_two.is_some() {
+            input.delta_two
+        } else {
+            input.delta
+        };
         let velocity = delta * player.sensitivity / delta_seconds + controller.velocity;
-        let damp = if motion_override.timer.tick(time.delta()).finished() {
+        let damp = if motion_override.timer.tick(clock.delta()).finished() {
             player.damp
         } else {
             motion_override.damp
@@ -89,9 +147,14 @@ pub fn move_player(
     }
 }
 
-#[allow(clippy::type_complexity, clippy::manual_clamp)]
+#[allow(
+    clippy::type_complexity,
+    clippy::too_many_arguments,
+    clippy::manual_clamp
+)]
 pub fn assist_player(
-    time: Res<Time>,
+    clock: Res<GameClock>,
+    assist_settings: Res<Assist>,
     mut time_scale: ResMut<TimeScale>,
     mut events: EventReader<CollisionEvent>,
     mut query: Query<
@@ -100,13 +163,24 @@ pub fn assist_player(
             &PlayerAssist,
             &mut Controller,
             &mut MotionOverride,
+            Option<&Autopilot>,
         ),
         (With<Player>, Without<Ball>),
     >,
     ball_query: Query<(&Motion, &Trajectory), With<Ball>>,
+    base_query: Query<(), With<PlayerBase>>,
+    player_two_query: Query<(), With<PlayerTwo>>,
 ) {
-    for (transform, assist, mut controller, _) in query.iter_mut() {
-        controller.velocity = Vec2::ZERO;
+    for (transform, assist, mut controller, _, autopilot) in query.iter_mut() {
+        controller.velocity = autopilot.map_or(Vec2::ZERO, |autopilot| autopilot.velocity);
+        // the assist would only help player one in versus
+        if !assist_settings.enabled || !player_two_query.is_empty() {
+            continue;
+        }
+
+        // with multiple balls, assist against the one arriving first and slow down for the most dangerous
+        let mut earliest_time = f32::MAX;
+        let mut target_time_scale: Option<f32> = None;
 
         for (motion, trajectory) in ball_query.iter() {
             let position = transform.translation.truncate();
@@ -117,10 +191,12 @@ pub fn assist_player(
                 && delta.x.abs() > assist.range
             {
                 // very dangerous, try to assist the player
-                let delta_seconds = time.elapsed_seconds() - trajectory.start_time;
+                let delta_seconds = clock.elapsed_seconds() - trajectory.start_time;
                 if let Some(candidate) = trajectory
                     .points
                     .iter()
+                    // the ball is lost once it reaches the base
+                    .take_while(|point| !point.bounce.is_some_and(|e| base_query.contains(e)))
                     .filter(|point| point.position.y < 0.0)
                     .filter(|point| point.position.y > -ARENA_HEIGHT / 2.0 + 16.0)
                     .filter(|point| point.velocity.y < 0.0)
@@ -146,31 +222,39 @@ pub fn assist_player(
                     if distance < stop_distance {
                         speed *= distance / stop_distance;
                     }
-                    controller.velocity = speed * direction.normalize();
-                    controller.velocity.y = 0.0;
+                    if time < earliest_time {
+                        earliest_time = time;
+                        controller.velocity = speed * direction.normalize();
+                        controller.velocity.y = 0.0;
+                    }
                 }
             }
 
-            let mut target_time_scale: f32 = 1.0;
+            let mut ball_time_scale: f32 = 1.0;
             if motion.velocity.y < assist.vertical_speed_threshold
                 && motion.velocity.length() > assist.speed_threshold
                 && delta.y > 0.0
             {
-                target_time_scale = target_time_scale
+                ball_time_scale = ball_time_scale
                     .min(delta.y / ARENA_HEIGHT * 2.0 - 0.25)
                     .max(0.2);
             }
+            target_time_scale =
+                Some(target_time_scale.map_or(ball_time_scale, |x| x.min(ball_time_scale)));
+        }
+
+        if let Some(target_time_scale) = target_time_scale {
             time_scale.0 =
                 time_scale
                     .0
-                    .damp(target_time_scale, TIME_SCALE_DAMP, time.delta_seconds());
+                    .damp(target_time_scale, TIME_SCALE_DAMP, clock.delta_seconds());
         }
     }
 
     // vertical impulse compensation
     let mut closure = |e1: Entity, e2: Entity| -> Option<()> {
         let _ = ball_query.get(e1).ok()?;
-        let (_, _, _, mut motion_override) = query.get_mut(e2).ok()?;
+        let (_, _, _, mut motion_override, _) = query.get_mut(e2).ok()?;
 
         motion_override.timer.reset();
 
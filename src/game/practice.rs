@@ -9,13 +9,12 @@ impl Plugin for PracticePlugin {
                 SystemSet::on_enter(AppState::Practice)
                     .with_system(enter_practice)
                     .with_system(make_arena)
-                    .with_system(make_ui)
                     .with_system(make_player)
                     .with_system(make_ball),
             )
             .add_system_set(
                 SystemSet::on_update(AppState::Practice)
-                    .with_system(escape_system)
+                    .with_system(pause_system)
                     .with_system(progress_system)
                     .with_system(change_slits)
                     .with_system(validate_slit_block)
@@ -40,50 +39,46 @@ pub enum PracticeState {
     Slits,
 }
 
-#[allow(clippy::too_many_arguments)]
 fn enter_practice(
+    clock: Res<GameClock>,
     mut practice_state: ResMut<State<PracticeState>>,
-    asset_server: Res<AssetServer>,
-    audio: Res<Audio>,
-    volume: Res<AudioVolume>,
-    mut music_track: ResMut<MusicTrack>,
+    mut rng: ResMut<GameRng>,
+    mut steps: ResMut<FixedSteps>,
     mut time_scale: ResMut<TimeScale>,
     mut heal_events: EventWriter<HealEvent>,
+    difficulty: Res<Difficulty>,
 ) {
     let _ = practice_state.set(PracticeState::Plain);
 
-    time_scale.reset();
-
-    heal_events.send(HealEvent(Heal::default()));
+    rng.start_run();
+    steps.reset(&clock);
 
-    if music_track.0 != GAME_MUSIC {
-        audio.stop();
-        audio.set_volume(volume.music.into());
-        audio.set_playback_rate(1.2);
-        audio.play(asset_server.load(GAME_MUSIC)).looped();
+    time_scale.reset();
 
-        music_track.0 = GAME_MUSIC;
-    }
+    heal_events.send(HealEvent(Heal::full(difficulty.preset().enemy_base_hp)));
 }
 
 /// Triggers a full recovery of enemy base health after beating it.
 fn recover_enemy_health(
-    time: Res<Time>,
+    clock: Res<GameClock>,
     mut game_over_events: EventReader<GameOverEvent>,
     mut game_over: Local<GameOver>,
     mut heal_events: EventWriter<HealEvent>,
+    difficulty: Res<Difficulty>,
 ) {
     if let Some(event) = game_over.event {
         // it's time to switch state
         if game_over
             .state_change_timer
-            .tick(time.delta())
+            .tick(clock.delta())
             .just_finished()
         {
             *game_over = GameOver::default();
 
             match event {
-                GameOverEvent::Win => heal_events.send(HealEvent(Heal::default())),
+                GameOverEvent::Win => {
+                    heal_events.send(HealEvent(Heal::full(difficulty.preset().enemy_base_hp)))
+                }
                 GameOverEvent::Lose => {}
             }
         }
@@ -102,7 +97,7 @@ fn player_ball_infinite(mut query: Query<&mut PlayerBase>) {
 }
 
 fn progress_system(
-    time: Res<Time>,
+    clock: Res<GameClock>,
     mut practice_state: ResMut<State<PracticeState>>,
     mut game_over_events: EventReader<GameOverEvent>,
     mut game_over: Local<GameOver>,
@@ -111,7 +106,7 @@ fn progress_system(
         // it's time to switch state
         if game_over
             .state_change_timer
-            .tick(time.delta())
+            .tick(clock.delta())
             .just_finished()
         {
             *game_over = GameOver::default();
@@ -124,7 +119,11 @@ fn progress_system(
     }
 }
 
-fn make_slit_blocks(mut commands: Commands, materials: Res<Materials>, mut slits: ResMut<Slits>) {
+fn make_slit_blocks(
+    mut commands: Commands,
+    images: Option<Res<AssetMap<Image>>>,
+    mut slits: ResMut<Slits>,
+) {
     let slits_index = slits.count / 2;
     slits.state = SlitState::Stand(slits_index);
 
@@ -134,56 +133,62 @@ fn make_slit_blocks(mut commands: Commands, materials: Res<Materials>, mut slits
             index,
         };
 
-        commands
-            .spawn((
-                SpriteBundle {
-                    transform: Transform::from_xyz(
-                        slit_block.position(slits_index),
-                        SLIT_POSITION_VERTICAL,
-                        0.1,
-                    ),
-                    sprite: Sprite {
-                        custom_size: Some(Vec2::new(SLIT_BLOCK_WIDTH, SLIT_BLOCK_HEIGHT)),
-                        color: PADDLE_COLOR,
-                        ..Default::default()
-                    },
+        let mut block = commands.spawn((
+            SpriteBundle {
+                transform: Transform::from_xyz(
+                    slit_block.position(slits_index),
+                    SLIT_POSITION_VERTICAL,
+                    0.1,
+                ),
+                sprite: Sprite {
+                    custom_size: Some(Vec2::new(SLIT_BLOCK_WIDTH, SLIT_BLOCK_HEIGHT)),
+                    color: PADDLE_COLOR,
                     ..Default::default()
                 },
-                RigidBody::new(
-                    Vec2::new(SLIT_BLOCK_WIDTH, SLIT_BLOCK_HEIGHT),
-                    0.0,
-                    1.0,
-                    0.0,
-                ),
-                PhysicsLayers::BOUNDARY,
-                BounceAudio::Bounce,
-                slit_block,
-                Cleanup,
-            ))
-            .with_children(|parent| {
+                ..Default::default()
+            },
+            RigidBody::new(
+                Shape::Box(Vec2::new(SLIT_BLOCK_WIDTH, SLIT_BLOCK_HEIGHT)),
+                0.0,
+                1.0,
+                0.0,
+            ),
+            PhysicsLayers::BOUNDARY,
+            BounceAudio::Bounce,
+            slit_block,
+            Cleanup,
+        ));
+
+        if let Some(images) = &images {
+            block.with_children(|parent| {
                 parent.spawn(SpriteBundle {
                     transform: Transform::from_xyz(-PADDLE_WIDTH / 2.0 + 8.0, 0.0, 0.1),
-                    texture: materials.enemy.clone(),
+                    texture: images.get("enemy"),
                     ..Default::default()
                 });
 
                 parent.spawn(SpriteBundle {
                     transform: Transform::from_xyz(PADDLE_WIDTH / 2.0 - 8.0, 0.0, 0.1),
-                    texture: materials.enemy.clone(),
+                    texture: images.get("enemy"),
                     ..Default::default()
                 });
             });
+        }
     }
 }
 
-fn change_slits(mut slits: ResMut<Slits>, mut player_hit_events: EventReader<PlayerHitEvent>) {
+fn change_slits(
+    mut rng: ResMut<GameRng>,
+    mut slits: ResMut<Slits>,
+    mut player_hit_events: EventReader<PlayerHitEvent>,
+) {
     for _ in player_hit_events.iter() {
         let previous = match &slits.state {
             SlitState::Stand(index) => *index,
             SlitState::Move { .. } => continue,
         };
 
-        let mut next = fastrand::usize(0..=slits.count);
+        let mut next = rng.gameplay().usize(0..=slits.count);
         if next == previous {
             next = (previous + 1) % (slits.count + 1);
         }
@@ -1,6 +1,8 @@
-use crate::{constants::*, utils::Interpolation, TimeScale};
+use crate::{
+    constants::*,
+    utils::{GameClock, Interpolation},
+};
 use bevy::prelude::*;
-use std::time::Duration;
 
 #[derive(Resource)]
 pub struct Slits {
@@ -57,10 +59,10 @@ pub fn move_slit_block(slits: Res<Slits>, mut query: Query<(&mut Transform, &Sli
     }
 }
 
-pub fn slits_system(time: Res<Time>, time_scale: Res<TimeScale>, mut slits: ResMut<Slits>) {
+pub fn slits_system(clock: Res<GameClock>, mut slits: ResMut<Slits>) {
     let switch = match &mut slits.state {
         SlitState::Move { next, timer, .. } => timer
-            .tick(Duration::from_secs_f32(time.delta_seconds() * time_scale.0))
+            .tick(clock.scaled_delta())
             .just_finished()
             .then_some(*next),
         _ => None,
@@ -0,0 +1,180 @@
+use super::*;
+
+/// Local two-player mode: player two controls the top paddle, and both bases count balls.
+pub struct VersusPlugin;
+
+impl Plugin for VersusPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GameMode>()
+            .init_resource::<Winner>()
+            .add_system_set(
+                SystemSet::on_enter(AppState::Versus)
+                    .with_system(enter_versus)
+                    .with_system(make_arena)
+                    .with_system(make_player)
+                    .with_system(make_player_two)
+                    .with_system(make_ball),
+            )
+            .add_system_set(
+                SystemSet::on_update(AppState::Versus)
+                    .with_system(pause_system)
+                    .with_system(reset_ball)
+                    .with_system(remove_ball)
+                    .with_system(player_miss)
+                    .with_system(game_over_system),
+            )
+            .add_system_set(
+                SystemSet::on_exit(AppState::Versus)
+                    .with_system(exit_versus)
+                    .with_system(cleanup_system::<Cleanup>),
+            );
+    }
+}
+
+/// What "Play" starts, chosen in the menu.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Resource)]
+pub enum GameMode {
+    /// Battle against the enemy.
+    #[default]
+    Solo,
+    /// Two players on one machine.
+    Versus,
+}
+
+impl GameMode {
+    pub const ALL: [Self; 2] = [Self::Solo, Self::Versus];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            GameMode::Solo => "Solo",
+            GameMode::Versus => "Versus",
+        }
+    }
+
+    pub fn state(&self) -> AppState {
+        match self {
+            GameMode::Solo => AppState::Battle,
+            GameMode::Versus => AppState::Versus,
+        }
+    }
+}
+
+/// Side that won the last versus run, named on the [`AppState::Winner`] screen.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Resource)]
+pub enum Winner {
+    /// The bottom paddle.
+    #[default]
+    PlayerOne,
+    /// The top paddle.
+    PlayerTwo,
+}
+
+impl Winner {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Winner::PlayerOne => "Player 1",
+            Winner::PlayerTwo => "Player 2",
+        }
+    }
+}
+
+fn enter_versus(
+    clock: Res<GameClock>,
+    mut rng: ResMut<GameRng>,
+    mut steps: ResMut<FixedSteps>,
+    mut time_scale: ResMut<TimeScale>,
+    mut score: ResMut<Score>,
+    mut player_two_input: ResMut<PlayerTwoInput>,
+) {
+    // clear score state
+    score.timestamp = clock.scaled_elapsed_seconds();
+    score.hits = 0;
+    score.miss = 0;
+    score.ball_count = PLAYER_BASE_BALL_COUNT;
+
+    rng.start_run();
+    steps.reset(&clock);
+
+    time_scale.reset();
+
+    player_two_input.enabled = true;
+}
+
+fn exit_versus(mut player_two_input: ResMut<PlayerTwoInput>) {
+    player_two_input.enabled = false;
+}
+
+fn make_player_two(mut commands: Commands, images: Option<Res<AssetMap<Image>>>) {
+    let mut player = commands.spawn((
+        SpriteBundle {
+            transform: Transform::from_xyz(0.0, 160.0, 0.0),
+            sprite: Sprite {
+                custom_size: Some(Vec2::new(PADDLE_WIDTH, PADDLE_HEIGHT)),
+                color: PADDLE_COLOR,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        RigidBody::new(
+            Shape::Box(Vec2::new(PADDLE_WIDTH, PADDLE_HEIGHT)),
+            3.0,
+            2.0,
+            1.0,
+        ),
+        Motion::default(),
+        PhysicsLayers::PLAYER,
+        BounceAudio::Bounce,
+        Controller::default(),
+        MotionOverride::default(),
+        Player::default(),
+        PlayerTwo,
+        Cleanup,
+    ));
+
+    if let Some(images) = images {
+        player.with_children(|parent| {
+            parent.spawn(SpriteBundle {
+                transform: Transform::from_xyz(-PADDLE_WIDTH / 2.0 + 8.0, 0.0, 0.1),
+                texture: images.get("enemy"),
+                ..Default::default()
+            });
+
+            parent.spawn(SpriteBundle {
+                transform: Transform::from_xyz(PADDLE_WIDTH / 2.0 - 8.0, 0.0, 0.1),
+                texture: images.get("enemy"),
+                ..Default::default()
+            });
+        });
+    }
+}
+
+/// Deals with [`GameOverEvent`] from the point of view of player one.
+/// Names the [`Winner`] and shows it after [`GAME_OVER_STATE_CHANGE_DURATION`].
+fn game_over_system(
+    clock: Res<GameClock>,
+    mut app_state: ResMut<State<AppState>>,
+    mut winner: ResMut<Winner>,
+    mut game_over_events: EventReader<GameOverEvent>,
+    mut game_over: Local<GameOver>,
+) {
+    if let Some(event) = game_over.event {
+        // it's time to switch state
+        if game_over
+            .state_change_timer
+            .tick(clock.delta())
+            .just_finished()
+        {
+            *game_over = GameOver::default();
+
+            *winner = match event {
+                GameOverEvent::Win => Winner::PlayerOne,
+                GameOverEvent::Lose => Winner::PlayerTwo,
+            };
+            app_state.set(AppState::Winner).unwrap();
+        }
+    } else {
+        for event in game_over_events.iter() {
+            game_over.event = Some(*event);
+        }
+    }
+}
@@ -0,0 +1,287 @@
+use crate::{
+    config::GameConfig,
+    game::{
+        BrainKind, Difficulty, EnemyBase, GameOverEvent, GamePlugin, MultiBall, PlayerBase, Score,
+        SelfPlay,
+    },
+    input::ControlPlugin,
+    utils::GameClock,
+    AppState, GameRng, TimeScale,
+};
+use bevy::{input::InputPlugin, prelude::*, time::TimeUpdateStrategy};
+use std::{fmt, time::Duration};
+
+#[derive(Debug, Clone)]
+pub struct HeadlessSettings {
+    /// Number of battle rounds to simulate.
+    pub rounds: usize,
+    /// Simulated duration of a frame in seconds.
+    pub frame_time: f32,
+    /// A round is abandoned after this many simulated seconds.
+    pub max_round_time: f32,
+    /// Seed of the first round; later rounds derive their seeds from it.
+    pub seed: Option<u64>,
+    pub difficulty: Difficulty,
+    /// Brain of the enemy, to compare opponents.
+    pub brain: BrainKind,
+    /// Plays the player's paddle with this brain instead of leaving it idle.
+    pub player_brain: Option<BrainKind>,
+    pub player_difficulty: Difficulty,
+    /// Lets extra balls join as the hits add up, as with the setting of the same name.
+    pub multi_ball: bool,
+}
+
+impl Default for HeadlessSettings {
+    fn default() -> Self {
+        Self {
+            rounds: 1,
+            frame_time: 1.0 / 60.0,
+            max_round_time: 600.0,
+            seed: None,
+            difficulty: Difficulty::default(),
+            brain: BrainKind::default(),
+            player_brain: None,
+            player_difficulty: Difficulty::default(),
+            multi_ball: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoundOutcome {
+    Win,
+    Lose,
+    Timeout,
+}
+
+impl From<GameOverEvent> for RoundOutcome {
+    fn from(event: GameOverEvent) -> Self {
+        match event {
+            GameOverEvent::Win => Self::Win,
+            GameOverEvent::Lose => Self::Lose,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RoundSummary {
+    pub round: usize,
+    /// Replays the round with [`crate::run_with_seed`] or `--seed`.
+    pub seed: u64,
+    pub outcome: RoundOutcome,
+    /// Simulated seconds from entering the battle to leaving it.
+    pub time: f32,
+    pub hits: i32,
+    pub miss: i32,
+    pub ball_count: i32,
+    pub enemy_hp: f32,
+}
+
+impl fmt::Display for RoundSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "round={} seed={} outcome={:?} time={:.2} hits={} miss={} ball_count={} enemy_hp={:.0}",
+            self.round,
+            self.seed,
+            self.outcome,
+            self.time,
+            self.hits,
+            self.miss,
+            self.ball_count,
+            self.enemy_hp
+        )
+    }
+}
+
+/// Results of all the rounds, from the player's side.
+#[derive(Debug, Clone)]
+pub struct HeadlessReport {
+    pub rounds: usize,
+    pub win_rate: f32,
+    pub lose_rate: f32,
+    pub timeout_rate: f32,
+    pub average_hits: f32,
+    pub average_miss: f32,
+    /// Average time of the won rounds, if any.
+    pub average_time_to_win: Option<f32>,
+}
+
+impl HeadlessReport {
+    pub fn new(summaries: &[RoundSummary]) -> Self {
+        let rounds = summaries.len();
+        let count = rounds.max(1) as f32;
+        let rate = |outcome: RoundOutcome| {
+            summaries
+                .iter()
+                .filter(|summary| summary.outcome == outcome)
+                .count() as f32
+                / count
+        };
+
+        let win_times = summaries
+            .iter()
+            .filter(|summary| summary.outcome == RoundOutcome::Win)
+            .map(|summary| summary.time)
+            .collect::<Vec<_>>();
+        let average_time_to_win =
+            (!win_times.is_empty()).then(|| win_times.iter().sum::<f32>() / win_times.len() as f32);
+
+        Self {
+            rounds,
+            win_rate: rate(RoundOutcome::Win),
+            lose_rate: rate(RoundOutcome::Lose),
+            timeout_rate: rate(RoundOutcome::Timeout),
+            average_hits: summaries
+                .iter()
+                .map(|summary| summary.hits as f32)
+                .sum::<f32>()
+                / count,
+            average_miss: summaries
+                .iter()
+                .map(|summary| summary.miss as f32)
+                .sum::<f32>()
+                / count,
+            average_time_to_win,
+        }
+    }
+}
+
+impl fmt::Display for HeadlessReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "rounds={} win_rate={:.2} lose_rate={:.2} timeout_rate={:.2} hits={:.2} miss={:.2} time_to_win=",
+            self.rounds,
+            self.win_rate,
+            self.lose_rate,
+            self.timeout_rate,
+            self.average_hits,
+            self.average_miss,
+        )?;
+        match self.average_time_to_win {
+            Some(time) => write!(f, "{time:.2}"),
+            None => write!(f, "-"),
+        }
+    }
+}
+
+#[derive(Resource)]
+struct Rounds {
+    settings: HeadlessSettings,
+    summaries: Vec<RoundSummary>,
+    outcome: Option<RoundOutcome>,
+}
+
+impl Rounds {
+    fn finished(&self) -> bool {
+        self.summaries.len() >= self.settings.rounds
+    }
+}
+
+/// Runs battle rounds without window, renderer or audio, stepping [`Time`] by a fixed frame time.
+/// Returns a summary for each round.
+pub fn run_headless(settings: HeadlessSettings) -> Vec<RoundSummary> {
+    let frame_time = Duration::from_secs_f32(settings.frame_time);
+    let rng = settings.seed.map(GameRng::new).unwrap_or_default();
+
+    // the same tuning as the windowed game, read without an asset server
+    #[cfg(not(target_arch = "wasm32"))]
+    let config = GameConfig::read_file().unwrap_or_default();
+    #[cfg(target_arch = "wasm32")]
+    let config = GameConfig::default();
+    GameConfig::set_current(config);
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(InputPlugin)
+        .add_plugin(ControlPlugin)
+        .init_resource::<TimeScale>()
+        .insert_resource(config.physics)
+        .insert_resource(rng)
+        .insert_resource(settings.difficulty)
+        .insert_resource(settings.brain)
+        .insert_resource(MultiBall {
+            enabled: settings.multi_ball,
+            ..Default::default()
+        })
+        .insert_resource(SelfPlay {
+            brain: settings.player_brain,
+            difficulty: settings.player_difficulty,
+        })
+        .insert_resource(Rounds {
+            settings,
+            summaries: vec![],
+            outcome: None,
+        })
+        .add_state(AppState::Battle)
+        .add_plugin(GamePlugin)
+        .add_system_set(SystemSet::on_update(AppState::Battle).with_system(watch_round))
+        .add_system_set(SystemSet::on_exit(AppState::Battle).with_system(record_round))
+        .add_system_set(SystemSet::on_update(AppState::Menu).with_system(next_round))
+        .add_system_set(SystemSet::on_update(AppState::Score).with_system(next_round));
+
+    // start from the startup instant of `Time`, so that no real time leaks into the first frame
+    let mut instant = app.world.resource::<Time>().startup();
+    while !app.world.resource::<Rounds>().finished() {
+        instant += frame_time;
+        app.insert_resource(TimeUpdateStrategy::ManualInstant(instant));
+        app.update();
+    }
+
+    app.world
+        .remove_resource::<Rounds>()
+        .map(|rounds| rounds.summaries)
+        .unwrap_or_default()
+}
+
+/// Remembers how the round ends, and abandons it if it takes too long.
+fn watch_round(
+    clock: Res<GameClock>,
+    score: Res<Score>,
+    mut rounds: ResMut<Rounds>,
+    mut app_state: ResMut<State<AppState>>,
+    mut game_over_events: EventReader<GameOverEvent>,
+) {
+    for event in game_over_events.iter() {
+        rounds.outcome = Some((*event).into());
+    }
+
+    if rounds.outcome.is_none() && score.time(&clock) > rounds.settings.max_round_time {
+        rounds.outcome = Some(RoundOutcome::Timeout);
+        app_state.set(AppState::Menu).unwrap();
+    }
+}
+
+fn record_round(
+    clock: Res<GameClock>,
+    score: Res<Score>,
+    rng: Res<GameRng>,
+    mut rounds: ResMut<Rounds>,
+    player_bases: Query<&PlayerBase>,
+    enemy_bases: Query<&EnemyBase>,
+) {
+    let summary = RoundSummary {
+        round: rounds.summaries.len(),
+        seed: rng.seed(),
+        outcome: rounds.outcome.take().unwrap_or(RoundOutcome::Timeout),
+        time: score.time(&clock),
+        hits: score.hits,
+        miss: score.miss,
+        ball_count: player_bases
+            .get_single()
+            .map(|base| base.ball_count)
+            .unwrap_or_default(),
+        enemy_hp: enemy_bases
+            .get_single()
+            .map(|base| base.hp)
+            .unwrap_or_default(),
+    };
+    rounds.summaries.push(summary);
+}
+
+fn next_round(rounds: Res<Rounds>, mut app_state: ResMut<State<AppState>>) {
+    if !rounds.finished() {
+        app_state.set(AppState::Battle).unwrap();
+    }
+}
@@ -0,0 +1,429 @@
+use crate::constants::*;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Reads the gamepad and the keyboard into [`ControlInput`], shared by the paddle and the menus,
+/// and into [`PlayerTwoInput`] in versus.
+pub struct ControlPlugin;
+
+impl Plugin for ControlPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<InputMap>()
+            .init_resource::<ControlInput>()
+            .init_resource::<PlayerTwoInput>()
+            .init_resource::<Rebinding>()
+            .add_system_to_stage(CoreStage::PreUpdate, read_control_input)
+            .add_system_to_stage(CoreStage::PreUpdate, read_player_two_input)
+            .add_system_to_stage(CoreStage::PreUpdate, rebind.after(read_control_input));
+    }
+}
+
+/// What the player can bind keys and gamepad buttons to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Action {
+    Up,
+    Down,
+    Left,
+    Right,
+    Confirm,
+    Pause,
+    /// Quits to the menu.
+    Back,
+    /// Directions of the second player in versus.
+    TwoUp,
+    TwoDown,
+    TwoLeft,
+    TwoRight,
+}
+
+impl Action {
+    pub const ALL: [Self; 11] = [
+        Self::Up,
+        Self::Down,
+        Self::Left,
+        Self::Right,
+        Self::Confirm,
+        Self::Pause,
+        Self::Back,
+        Self::TwoUp,
+        Self::TwoDown,
+        Self::TwoLeft,
+        Self::TwoRight,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Action::Up => "Up",
+            Action::Down => "Down",
+            Action::Left => "Left",
+            Action::Right => "Right",
+            Action::Confirm => "Select",
+            Action::Pause => "Pause",
+            Action::Back => "Quit",
+            Action::TwoUp => "P2 Up",
+            Action::TwoDown => "P2 Down",
+            Action::TwoLeft => "P2 Left",
+            Action::TwoRight => "P2 Right",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Binding {
+    Key(KeyCode),
+    Button(GamepadButtonType),
+}
+
+impl Binding {
+    pub fn name(&self) -> String {
+        match self {
+            Binding::Key(key) => format!("{key:?}"),
+            Binding::Button(button) => format!("{button:?}"),
+        }
+    }
+}
+
+/// Keys and gamepad buttons bound to each [`Action`], saved with the [`crate::settings::Settings`].
+#[derive(Debug, Clone, PartialEq, Resource, Serialize, Deserialize)]
+pub struct InputMap {
+    bindings: BTreeMap<Action, Vec<Binding>>,
+}
+
+impl Default for InputMap {
+    fn default() -> Self {
+        use Binding::*;
+        use GamepadButtonType::*;
+
+        Self {
+            bindings: BTreeMap::from([
+                (
+                    Action::Up,
+                    vec![Key(KeyCode::Up), Key(KeyCode::W), Button(DPadUp)],
+                ),
+                (
+                    Action::Down,
+                    vec![Key(KeyCode::Down), Key(KeyCode::S), Button(DPadDown)],
+                ),
+                (
+                    Action::Left,
+                    vec![Key(KeyCode::Left), Key(KeyCode::A), Button(DPadLeft)],
+                ),
+                (
+                    Action::Right,
+                    vec![Key(KeyCode::Right), Key(KeyCode::D), Button(DPadRight)],
+                ),
+                (
+                    Action::Confirm,
+                    vec![Key(KeyCode::Return), Key(KeyCode::Space), Button(South)],
+                ),
+                (Action::Pause, vec![Key(KeyCode::P), Button(Start)]),
+                (
+                    Action::Back,
+                    vec![Key(KeyCode::Escape), Button(East), Button(Select)],
+                ),
+                // the gamepad of player two uses the directions of player one
+                (Action::TwoUp, vec![Key(KeyCode::I)]),
+                (Action::TwoDown, vec![Key(KeyCode::K)]),
+                (Action::TwoLeft, vec![Key(KeyCode::J)]),
+                (Action::TwoRight, vec![Key(KeyCode::L)]),
+            ]),
+        }
+    }
+}
+
+impl InputMap {
+    pub fn bindings(&self, action: Action) -> &[Binding] {
+        self.bindings.get(&action).map_or(&[], Vec::as_slice)
+    }
+
+    /// Replaces the bindings of the action on the same device as `binding`,
+    /// keeping the ones on the other device.
+    pub fn rebind(&mut self, action: Action, binding: Binding) {
+        let bindings = self.bindings.entry(action).or_default();
+        bindings.retain(|other| std::mem::discriminant(other) != std::mem::discriminant(&binding));
+        bindings.push(binding);
+    }
+
+    /// The action other than `action` that `binding` is already bound to, if any.
+    pub fn conflict(&self, action: Action, binding: Binding) -> Option<Action> {
+        self.bindings
+            .iter()
+            .find(|(other, bindings)| **other != action && bindings.contains(&binding))
+            .map(|(other, _)| *other)
+    }
+
+    /// Gives the actions without any binding entry their default bindings.
+    pub fn bind_missing(&mut self) {
+        for (action, bindings) in Self::default().bindings {
+            self.bindings.entry(action).or_insert(bindings);
+        }
+    }
+
+    fn check(
+        &self,
+        action: Action,
+        key: impl Fn(KeyCode) -> bool,
+        button: impl Fn(GamepadButtonType) -> bool,
+    ) -> bool {
+        self.bindings(action).iter().any(|binding| match *binding {
+            Binding::Key(code) => key(code),
+            Binding::Button(button_type) => button(button_type),
+        })
+    }
+}
+
+/// The action waiting for its new binding, if any; the next key or gamepad button pressed is bound to it.
+#[derive(Default, Resource)]
+pub struct Rebinding {
+    pub action: Option<Action>,
+    /// The action already bound to the last refused press.
+    pub conflict: Option<Action>,
+}
+
+/// Gamepad and keyboard input of the current frame, as mapped by [`InputMap`].
+/// The mouse is read separately by the paddle.
+#[derive(Default, Resource)]
+pub struct ControlInput {
+    /// Requested movement, at most unit length.
+    /// Analog sticks map directly, while digital directions speed up as they are held.
+    pub direction: Vec2,
+    /// Digital direction pressed during this frame, to move between menu items.
+    pub navigate: IVec2,
+    /// Activates the focused menu item.
+    pub confirm: bool,
+    pub pause: bool,
+    /// Leaves the current screen.
+    pub back: bool,
+    /// Seconds the digital direction has been held.
+    held: f32,
+    /// Stick direction of the previous frame, so that tilting the stick navigates once.
+    stick: IVec2,
+}
+
+/// Paddle input of the second player in versus, from the player-two actions and [`PlayerTwoInput::gamepad`].
+#[derive(Default, Resource)]
+pub struct PlayerTwoInput {
+    /// Set while a versus run is in progress; the gamepad of player two then no longer moves player one.
+    pub enabled: bool,
+    /// Requested movement, at most unit length, like [`ControlInput::direction`].
+    pub direction: Vec2,
+    /// Seconds the digital direction has been held.
+    held: f32,
+}
+
+impl PlayerTwoInput {
+    /// With two gamepads or more, the last connected one belongs to player two;
+    /// a single gamepad stays with player one, and player two plays on the keyboard.
+    pub fn gamepad(gamepads: &Gamepads) -> Option<Gamepad> {
+        if gamepads.iter().count() < 2 {
+            return None;
+        }
+        gamepads.iter().max_by_key(|gamepad| gamepad.id)
+    }
+}
+
+/// Speed factor of a digital direction held for `held` seconds, easing in from [`CONTROL_RAMP_START`].
+fn acceleration(held: f32) -> f32 {
+    let t = (held / CONTROL_RAMP_TIME).min(1.0);
+    CONTROL_RAMP_START + (1.0 - CONTROL_RAMP_START) * t * t
+}
+
+fn axis(positive: bool, negative: bool) -> i32 {
+    match (positive, negative) {
+        (true, false) => 1,
+        (false, true) => -1,
+        _ => 0,
+    }
+}
+
+fn left_stick(axes: &Axis<GamepadAxis>, gamepad: Gamepad) -> Vec2 {
+    let get = |axis_type| {
+        axes.get(GamepadAxis::new(gamepad, axis_type))
+            .unwrap_or_default()
+    };
+    Vec2::new(
+        get(GamepadAxisType::LeftStickX),
+        get(GamepadAxisType::LeftStickY),
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn read_control_input(
+    time: Res<Time>,
+    input_map: Res<InputMap>,
+    keys: Res<Input<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    buttons: Res<Input<GamepadButton>>,
+    axes: Res<Axis<GamepadAxis>>,
+    player_two: Res<PlayerTwoInput>,
+    mut input: ResMut<ControlInput>,
+) {
+    // in versus, the gamepad of player two still works the menus, but does not move player one
+    let player_two_gamepad = player_two
+        .enabled
+        .then(|| PlayerTwoInput::gamepad(&gamepads))
+        .flatten();
+    let moving_gamepads = || {
+        gamepads
+            .iter()
+            .filter(|gamepad| Some(*gamepad) != player_two_gamepad)
+    };
+
+    let pressed = |action| {
+        input_map.check(
+            action,
+            |key| keys.pressed(key),
+            |button_type| {
+                moving_gamepads()
+                    .any(|gamepad| buttons.pressed(GamepadButton::new(gamepad, button_type)))
+            },
+        )
+    };
+    let just_pressed = |action| {
+        input_map.check(
+            action,
+            |key| keys.just_pressed(key),
+            |button_type| {
+                gamepads
+                    .iter()
+                    .any(|gamepad| buttons.just_pressed(GamepadButton::new(gamepad, button_type)))
+            },
+        )
+    };
+    let digital = IVec2::new(
+        axis(pressed(Action::Right), pressed(Action::Left)),
+        axis(pressed(Action::Up), pressed(Action::Down)),
+    );
+    let navigate = IVec2::new(
+        axis(just_pressed(Action::Right), just_pressed(Action::Left)),
+        axis(just_pressed(Action::Up), just_pressed(Action::Down)),
+    );
+
+    let analog = moving_gamepads()
+        .map(|gamepad| left_stick(&axes, gamepad))
+        .fold(Vec2::ZERO, |a, b| a + b)
+        .clamp_length_max(1.0);
+    let stick = IVec2::new(
+        axis(
+            analog.x > CONTROL_STICK_THRESHOLD,
+            analog.x < -CONTROL_STICK_THRESHOLD,
+        ),
+        axis(
+            analog.y > CONTROL_STICK_THRESHOLD,
+            analog.y < -CONTROL_STICK_THRESHOLD,
+        ),
+    );
+
+    if digital == IVec2::ZERO {
+        input.held = 0.0;
+    } else {
+        input.held += time.delta_seconds();
+    }
+
+    input.direction = (analog + digital.as_vec2().normalize_or_zero() * acceleration(input.held))
+        .clamp_length_max(1.0);
+    input.navigate = if navigate != IVec2::ZERO {
+        navigate
+    } else if stick != input.stick {
+        stick
+    } else {
+        IVec2::ZERO
+    };
+    input.stick = stick;
+    input.confirm = just_pressed(Action::Confirm);
+    input.pause = just_pressed(Action::Pause);
+    input.back = just_pressed(Action::Back);
+}
+
+pub fn read_player_two_input(
+    time: Res<Time>,
+    input_map: Res<InputMap>,
+    keys: Res<Input<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    buttons: Res<Input<GamepadButton>>,
+    axes: Res<Axis<GamepadAxis>>,
+    mut input: ResMut<PlayerTwoInput>,
+) {
+    if !input.enabled {
+        input.direction = Vec2::ZERO;
+        input.held = 0.0;
+        return;
+    }
+
+    let gamepad = PlayerTwoInput::gamepad(&gamepads);
+    let button = |button_type| {
+        gamepad.is_some_and(|gamepad| buttons.pressed(GamepadButton::new(gamepad, button_type)))
+    };
+    // the gamepad of player two also answers to the directions of player one
+    let pressed = |action, shared| {
+        input_map.check(action, |key| keys.pressed(key), button)
+            || input_map.check(shared, |_| false, button)
+    };
+
+    let digital = IVec2::new(
+        axis(
+            pressed(Action::TwoRight, Action::Right),
+            pressed(Action::TwoLeft, Action::Left),
+        ),
+        axis(
+            pressed(Action::TwoUp, Action::Up),
+            pressed(Action::TwoDown, Action::Down),
+        ),
+    );
+    let analog = gamepad.map_or(Vec2::ZERO, |gamepad| left_stick(&axes, gamepad));
+
+    if digital == IVec2::ZERO {
+        input.held = 0.0;
+    } else {
+        input.held += time.delta_seconds();
+    }
+
+    input.direction = (analog + digital.as_vec2().normalize_or_zero() * acceleration(input.held))
+        .clamp_length_max(1.0);
+}
+
+/// Binds the next key or gamepad button pressed to the action in [`Rebinding`].
+/// A press bound to [`Action::Back`] cancels, and one bound to another action is refused,
+/// so that every action keeps its bindings. The press is swallowed, so that it does not act on the menu too.
+fn rebind(
+    keys: Res<Input<KeyCode>>,
+    buttons: Res<Input<GamepadButton>>,
+    mut rebinding: ResMut<Rebinding>,
+    mut input_map: ResMut<InputMap>,
+    mut input: ResMut<ControlInput>,
+) {
+    let Some(action) = rebinding.action else {
+        return;
+    };
+
+    let binding = keys
+        .get_just_pressed()
+        .next()
+        .map(|key| Binding::Key(*key))
+        .or_else(|| {
+            buttons
+                .get_just_pressed()
+                .next()
+                .map(|button| Binding::Button(button.button_type))
+        });
+    let Some(binding) = binding else {
+        return;
+    };
+
+    if input_map.bindings(Action::Back).contains(&binding) {
+        rebinding.action = None;
+        rebinding.conflict = None;
+    } else if let Some(conflict) = input_map.conflict(action, binding) {
+        rebinding.conflict = Some(conflict);
+    } else {
+        input_map.rebind(action, binding);
+        rebinding.action = None;
+        rebinding.conflict = None;
+    }
+
+    input.navigate = IVec2::ZERO;
+    input.confirm = false;
+    input.pause = false;
+    input.back = false;
+}
@@ -1,23 +1,56 @@
-use bevy::{prelude::*, render::texture::ImageSampler, window::CursorGrabMode};
+use bevy::{
+    core_pipeline::clear_color::ClearColorConfig,
+    prelude::*,
+    render::{camera::ScalingMode, texture::ImageSampler},
+    utils::synccell::SyncCell,
+    window::CursorGrabMode,
+};
 use bevy_kira_audio::AudioPlugin;
+use serde::{Deserialize, Serialize};
+use settings::DisplaySettings;
 use wasm_bindgen::prelude::*;
 
 mod background;
+mod config;
 mod constants;
+mod display;
 mod effects;
 mod game;
+mod headless;
+mod input;
 mod loading;
 mod menu;
+mod records;
+mod replay;
+mod settings;
 mod utils;
 
+pub use game::{
+    Autopilot, BallSnapshot, BaseSnapshot, Brain, BrainKind, BroadPhase, Difficulty,
+    DifficultyPreset, Enemy, EnemyBrain, EnemySnapshot, HeuristicBrain, MaxTimeOfImpacts, Motion,
+    MultiBall, PhysicsLayers, PhysicsPlugin, PhysicsQuery, Point, QueryHit, RigidBody, SelfPlay,
+    Sensor, SmasherBrain, TrackerBrain, Trajectory, TriggerEnter, TriggerExit, TriggerStay,
+};
+pub use headless::{run_headless, HeadlessReport, HeadlessSettings, RoundOutcome, RoundSummary};
+pub use utils::Shape;
+
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub enum AppState {
     Loading,
     Menu,
     Settings,
+    History,
+    /// List of the saved runs, see [`replay::Replays`].
+    Replays,
     Battle,
     Practice,
+    /// Local two-player mode, see [`game::GameMode::Versus`].
+    Versus,
+    /// Pause menu, pushed over [`AppState::Battle`], [`AppState::Practice`] or [`AppState::Versus`].
+    Paused,
     Score,
+    /// End of a versus run, naming the [`game::Winner`].
+    Winner,
 }
 
 #[derive(Resource)]
@@ -35,12 +68,84 @@ impl TimeScale {
     }
 }
 
+/// Source of every random decision, seeded explicitly so that a run can be reproduced.
 #[derive(Resource)]
+pub struct GameRng {
+    master: SyncCell<fastrand::Rng>,
+    seed: u64,
+    started: bool,
+    queued: Option<u64>,
+    gameplay: SyncCell<fastrand::Rng>,
+    cosmetic: SyncCell<fastrand::Rng>,
+}
+
+impl Default for GameRng {
+    fn default() -> Self {
+        Self::new(fastrand::u64(..))
+    }
+}
+
+impl GameRng {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            master: SyncCell::new(fastrand::Rng::with_seed(seed)),
+            seed,
+            started: false,
+            queued: None,
+            gameplay: SyncCell::new(fastrand::Rng::with_seed(seed)),
+            cosmetic: SyncCell::new(fastrand::Rng::with_seed(!seed)),
+        }
+    }
+
+    /// Seed of the current run.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Makes the next run use the given seed, e.g. to replay a recorded run.
+    pub fn queue_run(&mut self, seed: u64) {
+        self.queued = Some(seed);
+    }
+
+    /// Reseeds game-play randomness for a new run.
+    /// The first run uses the initial seed, and later runs derive their seeds from it.
+    pub fn start_run(&mut self) {
+        if let Some(seed) = self.queued.take() {
+            self.seed = seed;
+        } else if self.started {
+            self.seed = self.master.get().u64(..);
+        }
+        self.started = true;
+        self.gameplay = SyncCell::new(fastrand::Rng::with_seed(self.seed));
+    }
+
+    /// Drives game-play decisions. Reseeded by [`GameRng::start_run`].
+    pub fn gameplay(&mut self) -> &fastrand::Rng {
+        self.gameplay.get()
+    }
+
+    /// Drives cosmetic choices (audio, hints) so that they never disturb game-play.
+    pub fn cosmetic(&mut self) -> &fastrand::Rng {
+        self.cosmetic.get()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Resource, Serialize, Deserialize)]
+#[serde(default)]
 pub struct AudioVolume {
     pub music: f32,
     pub effects: f32,
 }
 
+impl Default for AudioVolume {
+    fn default() -> Self {
+        Self {
+            music: 0.3,
+            effects: 1.0,
+        }
+    }
+}
+
 #[derive(Resource)]
 pub struct MusicTrack(&'static str);
 
@@ -87,15 +192,23 @@ impl HintText {
 
 #[wasm_bindgen]
 pub fn run() {
+    start(GameRng::default());
+}
+
+/// Runs the game with an explicit random seed, so that the runs can be reproduced.
+#[wasm_bindgen]
+pub fn run_with_seed(seed: u64) {
+    start(GameRng::new(seed));
+}
+
+fn start(rng: GameRng) {
     let mut app = App::new();
 
     app.insert_resource(ClearColor(Color::rgb(0.2, 0.2, 0.2)))
         .init_resource::<TimeScale>()
-        .insert_resource(AudioVolume {
-            music: 0.3,
-            effects: 1.0,
-        })
-        .insert_resource(MusicTrack(""));
+        .insert_resource(rng)
+        .insert_resource(MusicTrack(""))
+        .add_plugin(settings::SettingsPlugin);
 
     let default_plugins = DefaultPlugins
         .set(WindowPlugin {
@@ -103,13 +216,19 @@ pub fn run() {
                 title: "Bounce Up!".into(),
                 width: constants::ARENA_WIDTH,
                 height: constants::ARENA_HEIGHT,
-                resizable: false,
+                fit_canvas_to_parent: true,
+                mode: app.world.resource::<DisplaySettings>().window_mode(),
                 ..Default::default()
             },
             ..Default::default()
         })
         .set(ImagePlugin {
             default_sampler: ImageSampler::nearest_descriptor(),
+        })
+        .set(AssetPlugin {
+            // hot reloads the game config
+            watch_for_changes: cfg!(not(target_arch = "wasm32")),
+            ..Default::default()
         });
 
     #[cfg(feature = "dot")]
@@ -124,9 +243,15 @@ pub fn run() {
         .add_system(lock_release_cursor)
         .add_system(color_text_system)
         .add_system(hint_text_system)
+        .add_plugin(input::ControlPlugin)
+        .add_plugin(display::DisplayPlugin)
+        .add_plugin(config::ConfigPlugin)
         .add_plugin(loading::LoadingPlugin)
         .add_plugin(menu::MenuPlugin)
         .add_plugin(game::GamePlugin)
+        .add_plugin(game::PresentationPlugin)
+        .add_plugin(replay::ReplayPlugin)
+        .add_plugin(records::RecordsPlugin)
         .add_plugin(effects::EffectsPlugin)
         .add_plugin(background::BackgroundPlugin);
 
@@ -137,17 +262,43 @@ pub fn run() {
 }
 
 fn setup(mut commands: Commands) {
+    // the arena camera is letterboxed, see `display`
     commands.spawn((
-        Camera2dBundle::default(),
-        UiCameraConfig::default(),
+        Camera2dBundle {
+            projection: OrthographicProjection {
+                scaling_mode: ScalingMode::Auto {
+                    min_width: constants::ARENA_WIDTH,
+                    min_height: constants::ARENA_HEIGHT,
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        UiCameraConfig { show_ui: false },
         MainCamera,
     ));
+
+    // the UI is laid out over the whole window, so it is drawn by a camera without viewport
+    commands.spawn((
+        Camera2dBundle {
+            camera: Camera {
+                priority: 1,
+                ..Default::default()
+            },
+            camera_2d: Camera2d {
+                clear_color: ClearColorConfig::None,
+            },
+            ..Default::default()
+        },
+        UiCameraConfig::default(),
+        constants::UI_CAMERA_LAYER,
+    ));
 }
 
 fn lock_release_cursor(app_state: Res<State<AppState>>, mut windows: ResMut<Windows>) {
     if let Some(window) = windows.get_primary_mut() {
         match app_state.current() {
-            AppState::Battle | AppState::Practice => {
+            AppState::Battle | AppState::Practice | AppState::Versus => {
                 if cfg!(any(target_arch = "wasm32", target_os = "macos")) {
                     window.set_cursor_grab_mode(CursorGrabMode::Locked);
                 } else {
@@ -173,13 +324,17 @@ fn color_text_system(time: Res<Time>, mut query: Query<(&mut Text, &mut ColorTex
     }
 }
 
-pub fn hint_text_system(time: Res<Time>, mut query: Query<(&mut Text, &mut HintText)>) {
+pub fn hint_text_system(
+    time: Res<Time>,
+    mut rng: ResMut<GameRng>,
+    mut query: Query<(&mut Text, &mut HintText)>,
+) {
     for (mut text, mut hint) in query.iter_mut() {
         text.sections[0].value = HintText::HINT_TEXTS[hint.index].into();
 
         if hint.timer.tick(time.delta()).just_finished() {
             let len = HintText::HINT_TEXTS.len();
-            let mut next = fastrand::usize(0..len);
+            let mut next = rng.cosmetic().usize(0..len);
             if next == hint.index {
                 next = (hint.index + 1) % len;
             }
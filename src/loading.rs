@@ -1,78 +1,523 @@
 use crate::{constants::*, AppState};
-use bevy::{app::AppExit, prelude::*};
+use crate::{input::ControlInput, utils::cleanup_system};
+use bevy::{
+    asset::{Asset, AssetLoader, LoadContext, LoadState, LoadedAsset},
+    prelude::*,
+    reflect::TypeUuid,
+    utils::{BoxedFuture, HashMap},
+};
+use bevy_kira_audio::AudioSource;
+use serde::Deserialize;
+use std::collections::BTreeMap;
 
+/// Loads every asset listed in the [`AssetManifest`] before the menu, into the [`AssetMap`] resources.
+/// Shows the progress meanwhile, and the assets that failed to load with a retry button.
 pub struct LoadingPlugin;
 
 impl Plugin for LoadingPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<AssetsLoading>()
-            .add_system_set(SystemSet::on_enter(AppState::Loading).with_system(setup))
+        app.add_asset::<AssetManifest>()
+            .init_asset_loader::<AssetManifestLoader>()
+            .init_resource::<AssetsLoading>()
+            .init_resource::<AssetMap<Font>>()
+            .init_resource::<AssetMap<Image>>()
+            .init_resource::<AssetMap<TextureAtlas>>()
+            .init_resource::<AssetMap<AudioSource>>()
             .add_system_set(
-                SystemSet::on_update(AppState::Loading).with_system(check_assets_loaded),
+                SystemSet::on_enter(AppState::Loading)
+                    .with_system(setup)
+                    .with_system(make_loading_screen),
+            )
+            .add_system_set(
+                SystemSet::on_update(AppState::Loading)
+                    .with_system(load_manifest_assets)
+                    .with_system(check_assets_loaded.after(load_manifest_assets))
+                    .with_system(retry_loading.after(check_assets_loaded)),
+            )
+            .add_system_set(
+                SystemSet::on_exit(AppState::Loading).with_system(cleanup_system::<Cleanup>),
             );
     }
 }
 
-#[derive(Default, Resource, Deref, DerefMut)]
-struct AssetsLoading(Vec<HandleUntyped>);
+/// Paths of one or several assets under a single name.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+enum AssetPaths {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl AssetPaths {
+    fn iter(&self) -> impl Iterator<Item = &str> {
+        match self {
+            AssetPaths::One(path) => std::slice::from_ref(path),
+            AssetPaths::Many(paths) => paths.as_slice(),
+        }
+        .iter()
+        .map(String::as_str)
+    }
+}
+
+/// An image cut into a grid of equally sized tiles.
+#[derive(Debug, Clone, Deserialize)]
+struct AtlasEntry {
+    path: String,
+    tile_size: Vec2,
+    columns: usize,
+    rows: usize,
+}
+
+/// Every asset of the game by name, read from [`ASSET_MANIFEST`].
+#[derive(Debug, Clone, Default, Deserialize, TypeUuid)]
+#[uuid = "2d8c3f5e-7a41-4b0e-9f6a-1c5e8b3d9a27"]
+#[serde(default)]
+pub struct AssetManifest {
+    fonts: BTreeMap<String, AssetPaths>,
+    images: BTreeMap<String, AssetPaths>,
+    atlases: BTreeMap<String, AtlasEntry>,
+    audio: BTreeMap<String, AssetPaths>,
+    /// Music is not critical: the game goes on silently without the tracks that fail to load.
+    music: BTreeMap<String, AssetPaths>,
+}
+
+/// Names the game refers to, checked against the [`AssetManifest`] once it is loaded.
+/// Music is left out, as the game goes on without it.
+const REQUIRED_FONTS: &[&str] = &["arcade", "fira_mono", "invasion", "karmatic"];
+const REQUIRED_IMAGES: &[&str] = &[
+    "ball",
+    "death",
+    "enemy",
+    "exit_icon",
+    "help_icon",
+    "hint",
+    "player",
+    "reticle_icon",
+    "right_icon",
+    "wrench_icon",
+];
+const REQUIRED_ATLASES: &[&str] = &["hit"];
+const REQUIRED_AUDIO: &[&str] = &[
+    "button_click",
+    "button_hover",
+    "explosion",
+    "hit",
+    "impact",
+    "lose",
+    "miss",
+];
+
+impl AssetManifest {
+    /// The required names that the manifest lacks, described for the loading screen.
+    fn missing(&self) -> Vec<String> {
+        fn missing<'a, V>(
+            kind: &'a str,
+            required: &'a [&str],
+            entries: &'a BTreeMap<String, V>,
+        ) -> impl Iterator<Item = String> + 'a {
+            required
+                .iter()
+                .filter(|name| !entries.contains_key(**name))
+                .map(move |name| format!("{kind} \"{name}\" is missing from {ASSET_MANIFEST}"))
+        }
+
+        missing("font", REQUIRED_FONTS, &self.fonts)
+            .chain(missing("image", REQUIRED_IMAGES, &self.images))
+            .chain(missing("atlas", REQUIRED_ATLASES, &self.atlases))
+            .chain(missing("sound", REQUIRED_AUDIO, &self.audio))
+            .collect()
+    }
+}
+
+#[derive(Default)]
+struct AssetManifestLoader;
+
+impl AssetLoader for AssetManifestLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move {
+            let manifest: AssetManifest = ron::de::from_bytes(bytes)?;
+            load_context.set_default_asset(LoadedAsset::new(manifest));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["manifest.ron"]
+    }
+}
+
+/// Typed handles of the assets of type `T` in the [`AssetManifest`], by name.
+#[derive(Resource)]
+pub struct AssetMap<T: Asset> {
+    handles: HashMap<String, Vec<Handle<T>>>,
+}
+
+impl<T: Asset> Default for AssetMap<T> {
+    fn default() -> Self {
+        Self {
+            handles: HashMap::default(),
+        }
+    }
+}
+
+impl<T: Asset> AssetMap<T> {
+    /// The asset of the given name, or a default handle if the manifest lacks it.
+    /// The names the game needs are checked when the manifest is loaded, and the loading stops if one is missing.
+    pub fn get(&self, name: &str) -> Handle<T> {
+        self.try_get(name).unwrap_or_else(|| {
+            warn!("Missing asset {name} in {ASSET_MANIFEST}");
+            Handle::default()
+        })
+    }
+
+    /// The asset of the given name, if the manifest has it and it did not fail to load.
+    pub fn try_get(&self, name: &str) -> Option<Handle<T>> {
+        self.all(name).first().cloned()
+    }
+
+    /// Every asset listed under the given name.
+    pub fn all(&self, name: &str) -> &[Handle<T>] {
+        self.handles.get(name).map_or(&[], Vec::as_slice)
+    }
+
+    /// One of the assets listed under the given name, picked at random.
+    pub fn pick(&self, name: &str, rng: &fastrand::Rng) -> Handle<T> {
+        match self.all(name) {
+            [] => self.get(name),
+            handles => handles[rng.usize(..handles.len())].clone(),
+        }
+    }
+
+    fn insert(&mut self, name: &str, handle: Handle<T>) {
+        self.handles.entry(name.into()).or_default().push(handle);
+    }
+
+    fn load(
+        &mut self,
+        server: &AssetServer,
+        entries: &BTreeMap<String, AssetPaths>,
+        loading: &mut AssetsLoading,
+        critical: bool,
+    ) {
+        for (name, paths) in entries {
+            for path in paths.iter() {
+                let handle = server.load(path);
+                loading.push(path, handle.clone_untyped(), critical);
+                self.insert(name, handle);
+            }
+        }
+    }
+
+    /// Forgets the assets that failed to load, so that they are not used.
+    fn remove_failed(&mut self, server: &AssetServer) {
+        for handles in self.handles.values_mut() {
+            handles.retain(|handle| server.get_load_state(handle) != LoadState::Failed);
+        }
+    }
+}
+
+#[derive(Component)]
+struct Cleanup;
+
+#[derive(Component)]
+struct ProgressBar;
+
+#[derive(Component)]
+struct StatusText;
+
+#[derive(Component)]
+struct RetryButton;
+
+/// An asset being loaded, with its path to report it if it fails.
+struct LoadingAsset {
+    path: String,
+    handle: HandleUntyped,
+    /// The game can not start without a critical asset.
+    critical: bool,
+}
+
+#[derive(Default, Resource)]
+struct AssetsLoading {
+    manifest: Handle<AssetManifest>,
+    assets: Vec<LoadingAsset>,
+    /// Required names the manifest lacks, see [`AssetManifest::missing`].
+    missing: Vec<String>,
+    started: bool,
+}
+
+impl AssetsLoading {
+    fn push(&mut self, path: &str, handle: HandleUntyped, critical: bool) {
+        self.assets.push(LoadingAsset {
+            path: path.into(),
+            handle,
+            critical,
+        });
+    }
+}
+
+/// How far the loading is, from the [`LoadState`] of every asset.
+enum LoadingStatus {
+    Loading(f32),
+    /// Paths of the critical assets that failed to load, and the required names missing from the manifest.
+    Failed(Vec<String>),
+    Loaded,
+}
+
+impl LoadingStatus {
+    fn new(server: &AssetServer, loading: &AssetsLoading) -> Self {
+        let mut settled = 0;
+        let mut failed = loading.missing.clone();
+        for asset in &loading.assets {
+            match server.get_load_state(&asset.handle) {
+                LoadState::Loaded => settled += 1,
+                LoadState::Failed if asset.critical => failed.push(asset.path.clone()),
+                LoadState::Failed => settled += 1,
+                _ => {}
+            }
+        }
+
+        if !failed.is_empty() {
+            LoadingStatus::Failed(failed)
+        } else if !loading.started {
+            LoadingStatus::Loading(0.0)
+        } else if settled == loading.assets.len() {
+            LoadingStatus::Loaded
+        } else {
+            LoadingStatus::Loading(settled as f32 / loading.assets.len() as f32)
+        }
+    }
+}
 
 fn setup(server: Res<AssetServer>, mut loading: ResMut<AssetsLoading>) {
-    loading.push(server.load_untyped(BACKGROUND_SHADER));
+    loading.manifest = server.load(ASSET_MANIFEST);
+    let manifest = loading.manifest.clone_untyped();
+    loading.push(ASSET_MANIFEST, manifest, true);
+    // shaders are referred to by path
+    loading.push(
+        BACKGROUND_SHADER,
+        server.load_untyped(BACKGROUND_SHADER),
+        true,
+    );
+}
 
-    loading.push(server.load_untyped(FONT_FIRA_MONO));
-    loading.push(server.load_untyped(FONT_FIRA_SANS));
-    loading.push(server.load_untyped(FONT_ARCADE));
-    loading.push(server.load_untyped(FONT_KARMATIC));
-    loading.push(server.load_untyped(FONT_BOXY));
-    loading.push(server.load_untyped(FONT_ALAGARD));
-    loading.push(server.load_untyped(FONT_INVASION));
+fn make_loading_screen(mut commands: Commands, server: Res<AssetServer>) {
+    // the manifest is not loaded yet, neither are its fonts
+    let text_style = TextStyle {
+        font: server.load(LOADING_FONT),
+        font_size: 16.0,
+        color: Color::WHITE,
+    };
 
-    loading.push(server.load_untyped(PLAYER_SPRITE));
-    loading.push(server.load_untyped(ENEMY_SPRITE));
-    loading.push(server.load_untyped(BALL_SPRITE));
-    loading.push(server.load_untyped(HINT_SPRITE));
-    loading.push(server.load_untyped(DEATH_SPRITE));
-    loading.push(server.load_untyped(HIT_SPRITE));
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..Default::default()
+                },
+                background_color: Color::NONE.into(),
+                ..Default::default()
+            },
+            Cleanup,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        size: Size::new(Val::Percent(60.0), Val::Px(8.0)),
+                        ..Default::default()
+                    },
+                    background_color: LOADING_BAR_BACKGROUND_COLOR.into(),
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    parent.spawn((
+                        NodeBundle {
+                            style: Style {
+                                size: Size::new(Val::Percent(0.0), Val::Percent(100.0)),
+                                ..Default::default()
+                            },
+                            background_color: LOADING_BAR_COLOR.into(),
+                            ..Default::default()
+                        },
+                        ProgressBar,
+                    ));
+                });
 
-    loading.push(server.load_untyped(GAME_ICON));
-    loading.push(server.load_untyped(RIGHT_ICON));
-    loading.push(server.load_untyped(HELP_ICON));
-    loading.push(server.load_untyped(RETICLE_ICON));
-    loading.push(server.load_untyped(WRENCH_ICON));
-    loading.push(server.load_untyped(EXIT_ICON));
+            parent.spawn((
+                TextBundle {
+                    style: Style {
+                        margin: UiRect::all(Val::Px(20.0)),
+                        max_size: Size::new(Val::Percent(80.0), Val::Undefined),
+                        ..Default::default()
+                    },
+                    text: Text::from_section("Loading", text_style.clone()),
+                    ..Default::default()
+                },
+                StatusText,
+            ));
 
-    loading.push(server.load_untyped(HIT_AUDIO));
-    loading.push(server.load_untyped(MISS_AUDIO));
-    loading.push(server.load_untyped(EXPLOSION_AUDIO));
-    loading.push(server.load_untyped(LOSE_AUDIO));
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: Style {
+                            size: Size::new(Val::Px(120.0), Val::Px(30.0)),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            ..Default::default()
+                        },
+                        background_color: BUTTON_NORMAL_COLOR.into(),
+                        visibility: Visibility::INVISIBLE,
+                        ..Default::default()
+                    },
+                    RetryButton,
+                ))
+                .with_children(|parent| {
+                    parent.spawn(TextBundle {
+                        text: Text::from_section("Retry", text_style),
+                        ..Default::default()
+                    });
+                });
+        });
+}
 
-    for audio in IMPACT_AUDIOS {
-        loading.push(server.load_untyped(audio));
+/// Starts loading the assets of the manifest once it is loaded itself.
+#[allow(clippy::too_many_arguments)]
+fn load_manifest_assets(
+    server: Res<AssetServer>,
+    manifests: Res<Assets<AssetManifest>>,
+    mut loading: ResMut<AssetsLoading>,
+    mut fonts: ResMut<AssetMap<Font>>,
+    mut images: ResMut<AssetMap<Image>>,
+    mut atlases: ResMut<AssetMap<TextureAtlas>>,
+    mut texture_atlases: ResMut<Assets<TextureAtlas>>,
+    mut audios: ResMut<AssetMap<AudioSource>>,
+) {
+    if loading.started {
+        return;
+    }
+    let Some(manifest) = manifests.get(&loading.manifest) else {
+        return;
+    };
+
+    let loading = loading.as_mut();
+    loading.missing = manifest.missing();
+    for missing in &loading.missing {
+        error!("{missing}");
     }
+    fonts.load(&server, &manifest.fonts, loading, true);
+    images.load(&server, &manifest.images, loading, true);
+    audios.load(&server, &manifest.audio, loading, true);
+    audios.load(&server, &manifest.music, loading, false);
 
-    loading.push(server.load_untyped(BUTTON_HOVER_AUDIO));
-    loading.push(server.load_untyped(BUTTON_CLICK_AUDIO));
+    for (name, atlas) in &manifest.atlases {
+        let image = server.load(&atlas.path);
+        loading.push(&atlas.path, image.clone_untyped(), true);
+        atlases.insert(
+            name,
+            texture_atlases.add(TextureAtlas::from_grid(
+                image,
+                atlas.tile_size,
+                atlas.columns,
+                atlas.rows,
+                None,
+                None,
+            )),
+        );
+    }
 
-    loading.push(server.load_untyped(MENU_MUSIC));
-    loading.push(server.load_untyped(GAME_MUSIC));
+    loading.started = true;
 }
 
+#[allow(clippy::too_many_arguments)]
 fn check_assets_loaded(
     server: Res<AssetServer>,
     loading: Res<AssetsLoading>,
     mut app_state: ResMut<State<AppState>>,
-    mut exit: EventWriter<AppExit>,
+    mut audios: ResMut<AssetMap<AudioSource>>,
+    mut bars: Query<&mut Style, With<ProgressBar>>,
+    mut texts: Query<&mut Text, With<StatusText>>,
+    mut buttons: Query<&mut Visibility, With<RetryButton>>,
 ) {
-    use bevy::asset::LoadState;
-    match server.get_group_load_state(loading.iter().map(|handle| handle.id)) {
-        LoadState::Loaded => {
+    let status = LoadingStatus::new(&server, &loading);
+
+    let text = match &status {
+        LoadingStatus::Loading(progress) => format!("Loading {:.0}%", 100.0 * progress),
+        LoadingStatus::Failed(paths) => format!("Failed to load\n{}", paths.join("\n")),
+        LoadingStatus::Loaded => {
+            for asset in &loading.assets {
+                if server.get_load_state(&asset.handle) == LoadState::Failed {
+                    warn!("Going on without {}", asset.path);
+                }
+            }
+            audios.remove_failed(&server);
+
             info!("Assets Loaded");
             app_state.set(AppState::Menu).unwrap();
+            return;
+        }
+    };
+
+    let progress = match status {
+        LoadingStatus::Loading(progress) => progress,
+        _ => 0.0,
+    };
+    for mut style in bars.iter_mut() {
+        style.size.width = Val::Percent(100.0 * progress);
+    }
+    for mut status_text in texts.iter_mut() {
+        if status_text.sections[0].value != text {
+            status_text.sections[0].value = text.clone();
+        }
+    }
+    for mut visibility in buttons.iter_mut() {
+        visibility.is_visible = matches!(status, LoadingStatus::Failed(_));
+    }
+}
+
+/// Loads the failed assets again, on the retry button or the confirm input.
+#[allow(clippy::type_complexity)]
+fn retry_loading(
+    server: Res<AssetServer>,
+    loading: Res<AssetsLoading>,
+    input: Res<ControlInput>,
+    mut buttons: Query<
+        (&Interaction, &Visibility, &mut BackgroundColor, &Children),
+        (Changed<Interaction>, With<RetryButton>),
+    >,
+    mut text_query: Query<&mut Text>,
+) {
+    let mut retry = false;
+    for (interaction, visibility, mut color, children) in buttons.iter_mut() {
+        let (background, text_color) = match interaction {
+            Interaction::Clicked => (BUTTON_PRESSED_COLOR, BUTTON_TEXT_PRESSED_COLOR),
+            Interaction::Hovered => (BUTTON_HOVERED_COLOR, BUTTON_TEXT_HOVERED_COLOR),
+            Interaction::None => (BUTTON_NORMAL_COLOR, BUTTON_TEXT_NORMAL_COLOR),
+        };
+        *color = background.into();
+        for child in children.iter() {
+            if let Ok(mut text) = text_query.get_mut(*child) {
+                text.sections[0].style.color = text_color;
+            }
         }
-        LoadState::Failed => {
-            info!("Assets Loading Failed");
-            exit.send(AppExit);
+
+        retry |= visibility.is_visible && *interaction == Interaction::Clicked;
+    }
+    if !retry && !input.confirm {
+        return;
+    }
+
+    for asset in &loading.assets {
+        if asset.critical && server.get_load_state(&asset.handle) == LoadState::Failed {
+            info!("Retrying {}", asset.path);
+            server.reload_asset(asset.path.as_str());
         }
-        _ => {}
     }
 }
@@ -1,3 +1,58 @@
+use bounce_up::{BrainKind, Difficulty, HeadlessReport, HeadlessSettings};
+
 fn main() {
-    bounce_up::run();
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let value = |name: &str| {
+        args.iter()
+            .position(|arg| arg == name)
+            .and_then(|index| args.get(index + 1))
+    };
+
+    let seed = value("--seed").map(|seed| seed.parse().expect("--seed expects a number"));
+
+    if args.iter().any(|arg| arg == "--headless") {
+        let mut settings = HeadlessSettings {
+            seed,
+            multi_ball: args.iter().any(|arg| arg == "--multi-ball"),
+            ..Default::default()
+        };
+        if let Some(rounds) = value("--rounds") {
+            settings.rounds = rounds.parse().expect("--rounds expects a number");
+        }
+
+        let difficulty = |name: &str| {
+            Difficulty::ALL
+                .into_iter()
+                .find(|difficulty| difficulty.name().eq_ignore_ascii_case(name))
+                .expect("difficulty expects easy, normal, hard or nightmare")
+        };
+        let brain = |name: &str| {
+            BrainKind::ALL
+                .into_iter()
+                .find(|brain| brain.name().eq_ignore_ascii_case(name))
+                .expect("brain expects heuristic, tracker or smasher")
+        };
+        if let Some(name) = value("--difficulty") {
+            settings.difficulty = difficulty(name);
+        }
+        if let Some(name) = value("--brain") {
+            settings.brain = brain(name);
+        }
+        if let Some(name) = value("--player-difficulty") {
+            settings.player_difficulty = difficulty(name);
+        }
+        if let Some(name) = value("--player-brain") {
+            settings.player_brain = Some(brain(name));
+        }
+
+        let summaries = bounce_up::run_headless(settings);
+        for summary in &summaries {
+            println!("{summary}");
+        }
+        println!("{}", HeadlessReport::new(&summaries));
+    } else if let Some(seed) = seed {
+        bounce_up::run_with_seed(seed);
+    } else {
+        bounce_up::run();
+    }
 }
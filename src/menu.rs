@@ -1,11 +1,16 @@
 use crate::{
     constants::*,
-    game::Score,
-    utils::{cleanup_system, escape_system},
-    AppState, AudioVolume, ColorText, HintText, MusicTrack, TimeScale,
+    game::{Assist, Difficulty, GameMode, MultiBall, Score, Winner},
+    input::{Action, ControlInput, InputMap, Rebinding},
+    loading::AssetMap,
+    records::{RecordSystems, Records},
+    replay::{Replays, RunSettingsParam},
+    settings::DisplaySettings,
+    utils::{cleanup_system, escape_system, pause_system, GameClock},
+    AppState, AudioVolume, ColorText, GameRng, HintText, MusicTrack, TimeScale,
 };
 use bevy::prelude::*;
-use bevy_kira_audio::{Audio, AudioApp, AudioChannel, AudioControl};
+use bevy_kira_audio::{Audio, AudioApp, AudioChannel, AudioControl, AudioSource};
 
 pub struct MenuPlugin;
 
@@ -15,16 +20,21 @@ struct ButtonAudio;
 impl Plugin for MenuPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<ButtonStyle>()
+            .init_resource::<MenuFocus>()
             .add_audio_channel::<ButtonAudio>()
+            .add_system(navigate_menu.before(ButtonSystems))
             .add_system_set(
                 SystemSet::new()
                     .label(ButtonSystems)
                     .with_system(button_system)
                     .with_system(button_action)
                     .with_system(value_system)
-                    .with_system(value_action),
+                    .with_system(value_action)
+                    .with_system(binding_text)
+                    .with_system(record_text),
             )
             .add_system(button_audio.after(ButtonSystems))
+            .add_system_set(SystemSet::on_exit(AppState::Loading).with_system(style_buttons))
             .add_system_set(
                 SystemSet::on_enter(AppState::Menu)
                     .with_system(enter_menu)
@@ -33,18 +43,66 @@ impl Plugin for MenuPlugin {
             .add_system_set(
                 SystemSet::on_exit(AppState::Menu).with_system(cleanup_system::<Cleanup>),
             )
+            .add_system_set(
+                SystemSet::on_enter(AppState::Paused)
+                    .with_system(enter_pause)
+                    .with_system(make_pause),
+            )
+            .add_system_set(SystemSet::on_update(AppState::Paused).with_system(pause_system))
+            .add_system_set(
+                SystemSet::on_exit(AppState::Paused)
+                    .with_system(cleanup_system::<Cleanup>)
+                    .with_system(exit_pause),
+            )
+            // the pause menu makes way for the settings opened from it
+            .add_system_set(
+                SystemSet::on_pause(AppState::Paused).with_system(cleanup_system::<Cleanup>),
+            )
+            .add_system_set(SystemSet::on_resume(AppState::Paused).with_system(make_pause))
             .add_system_set(SystemSet::on_enter(AppState::Settings).with_system(make_settings))
             .add_system_set(SystemSet::on_update(AppState::Settings).with_system(escape_system))
             .add_system_set(
-                SystemSet::on_exit(AppState::Settings).with_system(cleanup_system::<Cleanup>),
+                SystemSet::on_exit(AppState::Settings)
+                    .with_system(cleanup_system::<Cleanup>)
+                    .with_system(cancel_rebinding),
+            )
+            .add_system_set(
+                SystemSet::on_enter(AppState::History)
+                    .with_system(enter_history)
+                    .with_system(make_history),
+            )
+            .add_system_set(SystemSet::on_update(AppState::History).with_system(escape_system))
+            .add_system_set(
+                SystemSet::on_exit(AppState::History)
+                    .with_system(cleanup_system::<Cleanup>)
+                    .with_system(exit_history),
+            )
+            .add_system_set(
+                SystemSet::on_enter(AppState::Replays)
+                    .with_system(enter_replays)
+                    .with_system(make_replays.after(enter_replays)),
+            )
+            .add_system_set(SystemSet::on_update(AppState::Replays).with_system(escape_system))
+            .add_system_set(
+                SystemSet::on_exit(AppState::Replays).with_system(cleanup_system::<Cleanup>),
             )
             .add_system_set(
                 SystemSet::on_enter(AppState::Score)
                     .with_system(enter_score)
-                    .with_system(make_score),
+                    .with_system(make_score.after(RecordSystems)),
             )
+            .add_system_set(SystemSet::on_update(AppState::Score).with_system(initials_text))
             .add_system_set(
                 SystemSet::on_exit(AppState::Score).with_system(cleanup_system::<Cleanup>),
+            )
+            .add_system_set(
+                SystemSet::on_enter(AppState::Winner)
+                    .with_system(enter_score)
+                    .with_system(make_winner),
+            )
+            .add_system_set(SystemSet::on_update(AppState::Winner).with_system(escape_system))
+            .add_system_set(
+                SystemSet::on_exit(AppState::Winner).with_system(cleanup_system::<Cleanup>),
             );
     }
 }
@@ -58,15 +116,76 @@ pub struct ButtonSystems;
 #[derive(Clone, Copy, Component)]
 enum ButtonAction {
     Play,
+    Replay,
+    /// Plays the saved run at the index in [`Replays::saved`].
+    PlayReplay(usize),
     Tutorial,
     Settings,
+    History,
+    Rebind(Action),
     Back,
+    Resume,
+    Restart,
+    Quit,
 }
 
 #[derive(Clone, Copy, Component)]
 enum ValueAction {
     AudioVolume(f32),
     MusicVolume(f32),
+    Difficulty(Difficulty),
+    Mode(GameMode),
+    Assist(bool),
+    MultiBall(bool),
+    Fullscreen(bool),
+    History(Difficulty),
+}
+
+#[derive(Component)]
+struct DifficultyText;
+
+#[derive(Component)]
+struct ModeText;
+
+#[derive(Component)]
+struct AssistText;
+
+#[derive(Component)]
+struct MultiBallText;
+
+#[derive(Component)]
+struct FullscreenText;
+
+#[derive(Component)]
+struct BindingText(Action);
+
+#[derive(Component)]
+struct InitialsText;
+
+/// A row of the high score table on the history page.
+#[derive(Component)]
+struct RecordText(usize);
+
+/// Difficulty whose high score table the history page shows.
+#[derive(Resource)]
+struct HistoryDifficulty(Difficulty);
+
+/// Menu item chosen with the gamepad or the keyboard, shown as if hovered by the mouse.
+#[derive(Default, Resource)]
+struct MenuFocus(Option<Entity>);
+
+impl MenuFocus {
+    fn interaction(&self, entity: Entity, interaction: &Interaction) -> Interaction {
+        if *interaction == Interaction::None && self.0 == Some(entity) {
+            Interaction::Hovered
+        } else {
+            *interaction
+        }
+    }
+
+    fn confirmed(&self, entity: Entity, input: &ControlInput) -> bool {
+        input.confirm && self.0 == Some(entity)
+    }
 }
 
 #[derive(Resource)]
@@ -76,9 +195,8 @@ struct ButtonStyle {
     text: TextStyle,
 }
 
-impl FromWorld for ButtonStyle {
-    fn from_world(world: &mut World) -> Self {
-        let asset_server = world.resource::<AssetServer>();
+impl Default for ButtonStyle {
+    fn default() -> Self {
         ButtonStyle {
             button: Style {
                 size: Size::new(Val::Px(200.0), Val::Px(30.0)),
@@ -107,7 +225,7 @@ impl FromWorld for ButtonStyle {
                 ..Default::default()
             },
             text: TextStyle {
-                font: asset_server.load(FONT_KARMATIC),
+                font: Default::default(),
                 font_size: 20.0,
                 color: BUTTON_TEXT_NORMAL_COLOR,
             },
@@ -115,28 +233,38 @@ impl FromWorld for ButtonStyle {
     }
 }
 
+/// Sets the font of the buttons once it is loaded.
+fn style_buttons(fonts: Res<AssetMap<Font>>, mut button_style: ResMut<ButtonStyle>) {
+    button_style.text.font = fonts.get("karmatic");
+}
+
 fn enter_menu(
     mut time_scale: ResMut<TimeScale>,
-    asset_server: Res<AssetServer>,
+    audios: Res<AssetMap<AudioSource>>,
     audio: Res<Audio>,
     volume: Res<AudioVolume>,
     mut music_track: ResMut<MusicTrack>,
 ) {
     time_scale.reset();
-    if music_track.0 != MENU_MUSIC {
+    if music_track.0 != "menu_music" {
         audio.stop();
         audio.set_playback_rate(1.0);
         audio.set_volume(volume.music.into());
-        audio.play(asset_server.load(MENU_MUSIC)).looped();
+        // music may have failed to load
+        if let Some(music) = audios.try_get("menu_music") {
+            audio.play(music).looped();
+        }
 
-        music_track.0 = MENU_MUSIC;
+        music_track.0 = "menu_music";
     }
 }
 
 fn make_menu(
     mut commands: Commands,
-    asset_server: Res<AssetServer>,
+    fonts: Res<AssetMap<Font>>,
+    images: Res<AssetMap<Image>>,
     button_style: Res<ButtonStyle>,
+    game_mode: Res<GameMode>,
 ) {
     commands
         .spawn((
@@ -169,7 +297,7 @@ fn make_menu(
                     text: Text::from_section(
                         "Bounce Up!",
                         TextStyle {
-                            font: asset_server.load(FONT_ARCADE),
+                            font: fonts.get("arcade"),
                             font_size: 50.0,
                             color: Color::WHITE,
                         },
@@ -197,7 +325,7 @@ fn make_menu(
                     text: Text::from_section(
                         "",
                         TextStyle {
-                            font: asset_server.load(FONT_INVASION),
+                            font: fonts.get("invasion"),
                             font_size: 15.0,
                             color: HEALTH_BAR_COLOR,
                         },
@@ -223,7 +351,7 @@ fn make_menu(
                 .with_children(|parent| {
                     parent.spawn(ImageBundle {
                         style: button_style.icon.clone(),
-                        image: UiImage(asset_server.load(RIGHT_ICON)),
+                        image: UiImage(images.get("right_icon")),
                         ..Default::default()
                     });
                     parent.spawn(TextBundle {
@@ -231,6 +359,104 @@ fn make_menu(
                         ..Default::default()
                     });
                 });
+            // game mode, started by "Play"
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        size: Size::new(Val::Percent(100.0), Val::Px(40.0)),
+                        align_items: AlignItems::Center,
+                        ..Default::default()
+                    },
+                    background_color: Color::NONE.into(),
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    parent.spawn(TextBundle {
+                        style: Style {
+                            position: UiRect {
+                                left: Val::Percent(10.0),
+                                ..Default::default()
+                            },
+                            margin: UiRect {
+                                right: Val::Percent(10.0),
+                                ..Default::default()
+                            },
+                            ..Default::default()
+                        },
+                        text: Text::from_section(
+                            "Mode",
+                            TextStyle {
+                                font: fonts.get("karmatic"),
+                                font_size: 20.0,
+                                color: Color::WHITE,
+                            },
+                        )
+                        .with_alignment(TextAlignment {
+                            horizontal: HorizontalAlign::Center,
+                            ..Default::default()
+                        }),
+                        ..Default::default()
+                    });
+                    for mode_setting in GameMode::ALL {
+                        parent.spawn((
+                            ButtonBundle {
+                                style: Style {
+                                    size: Size::new(Val::Px(20.0), Val::Px(20.0)),
+                                    margin: UiRect {
+                                        left: Val::Px(2.0),
+                                        right: Val::Px(2.0),
+                                        ..Default::default()
+                                    },
+                                    ..button_style.button.clone()
+                                },
+                                background_color: SETTING_NORMAL_COLOR.into(),
+                                ..Default::default()
+                            },
+                            ValueAction::Mode(mode_setting),
+                        ));
+                    }
+                    parent.spawn((
+                        TextBundle {
+                            style: Style {
+                                margin: UiRect {
+                                    left: Val::Px(10.0),
+                                    ..Default::default()
+                                },
+                                ..Default::default()
+                            },
+                            text: Text::from_section(
+                                game_mode.name(),
+                                TextStyle {
+                                    font: fonts.get("karmatic"),
+                                    font_size: 20.0,
+                                    color: Color::GOLD,
+                                },
+                            ),
+                            ..Default::default()
+                        },
+                        ModeText,
+                    ));
+                });
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: button_style.button.clone(),
+                        background_color: BUTTON_NORMAL_COLOR.into(),
+                        ..Default::default()
+                    },
+                    ButtonAction::Replay,
+                ))
+                .with_children(|parent| {
+                    parent.spawn(ImageBundle {
+                        style: button_style.icon.clone(),
+                        image: UiImage(images.get("right_icon")),
+                        ..Default::default()
+                    });
+                    parent.spawn(TextBundle {
+                        text: Text::from_section("Replay", button_style.text.clone()),
+                        ..Default::default()
+                    });
+                });
             parent
                 .spawn((
                     ButtonBundle {
@@ -243,7 +469,7 @@ fn make_menu(
                 .with_children(|parent| {
                     parent.spawn(ImageBundle {
                         style: button_style.icon.clone(),
-                        image: UiImage(asset_server.load(RETICLE_ICON)),
+                        image: UiImage(images.get("reticle_icon")),
                         ..Default::default()
                     });
                     parent.spawn(TextBundle {
@@ -263,7 +489,7 @@ fn make_menu(
                 .with_children(|parent| {
                     parent.spawn(ImageBundle {
                         style: button_style.icon.clone(),
-                        image: UiImage(asset_server.load(WRENCH_ICON)),
+                        image: UiImage(images.get("wrench_icon")),
                         ..Default::default()
                     });
                     parent.spawn(TextBundle {
@@ -271,13 +497,123 @@ fn make_menu(
                         ..Default::default()
                     });
                 });
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: button_style.button.clone(),
+                        background_color: BUTTON_NORMAL_COLOR.into(),
+                        ..Default::default()
+                    },
+                    ButtonAction::History,
+                ))
+                .with_children(|parent| {
+                    parent.spawn(ImageBundle {
+                        style: button_style.icon.clone(),
+                        image: UiImage(images.get("help_icon")),
+                        ..Default::default()
+                    });
+                    parent.spawn(TextBundle {
+                        text: Text::from_section("History", button_style.text.clone()),
+                        ..Default::default()
+                    });
+                });
+        });
+}
+
+fn enter_pause(audio: Res<Audio>) {
+    audio.pause();
+}
+
+fn exit_pause(audio: Res<Audio>) {
+    audio.resume();
+}
+
+/// Overlay of the pause menu, over the frozen arena.
+fn make_pause(
+    mut commands: Commands,
+    fonts: Res<AssetMap<Font>>,
+    images: Res<AssetMap<Image>>,
+    button_style: Res<ButtonStyle>,
+) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    ..Default::default()
+                },
+                background_color: PAUSE_BACKGROUND_COLOR.into(),
+                ..Default::default()
+            },
+            Cleanup,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle {
+                style: Style {
+                    position: UiRect {
+                        left: Val::Percent(10.0),
+                        ..Default::default()
+                    },
+                    margin: UiRect {
+                        bottom: Val::Percent(10.0),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                text: Text::from_section(
+                    "Paused",
+                    TextStyle {
+                        font: fonts.get("arcade"),
+                        font_size: 50.0,
+                        color: Color::WHITE,
+                    },
+                ),
+                ..Default::default()
+            });
+
+            for (action, icon, text) in [
+                (ButtonAction::Resume, "right_icon", "Resume"),
+                (ButtonAction::Restart, "reticle_icon", "Restart"),
+                (ButtonAction::Settings, "wrench_icon", "Settings"),
+                (ButtonAction::Quit, "exit_icon", "Quit"),
+            ] {
+                parent
+                    .spawn((
+                        ButtonBundle {
+                            style: button_style.button.clone(),
+                            background_color: BUTTON_NORMAL_COLOR.into(),
+                            ..Default::default()
+                        },
+                        action,
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn(ImageBundle {
+                            style: button_style.icon.clone(),
+                            image: UiImage(images.get(icon)),
+                            ..Default::default()
+                        });
+                        parent.spawn(TextBundle {
+                            text: Text::from_section(text, button_style.text.clone()),
+                            ..Default::default()
+                        });
+                    });
+            }
         });
 }
 
+#[allow(clippy::too_many_arguments)]
 fn make_settings(
     mut commands: Commands,
-    asset_server: Res<AssetServer>,
+    fonts: Res<AssetMap<Font>>,
+    images: Res<AssetMap<Image>>,
     button_style: Res<ButtonStyle>,
+    difficulty: Res<Difficulty>,
+    assist: Res<Assist>,
+    multi_ball: Res<MultiBall>,
+    display: Res<DisplaySettings>,
+    input_map: Res<InputMap>,
 ) {
     commands
         .spawn((
@@ -309,7 +645,7 @@ fn make_settings(
                 text: Text::from_section(
                     "Settings",
                     TextStyle {
-                        font: asset_server.load(FONT_KARMATIC),
+                        font: fonts.get("karmatic"),
                         font_size: 30.0,
                         color: Color::WHITE,
                     },
@@ -348,7 +684,7 @@ fn make_settings(
                         text: Text::from_section(
                             "Audio",
                             TextStyle {
-                                font: asset_server.load(FONT_KARMATIC),
+                                font: fonts.get("karmatic"),
                                 font_size: 20.0,
                                 color: Color::WHITE,
                             },
@@ -406,7 +742,7 @@ fn make_settings(
                         text: Text::from_section(
                             "Music",
                             TextStyle {
-                                font: asset_server.load(FONT_KARMATIC),
+                                font: fonts.get("karmatic"),
                                 font_size: 20.0,
                                 color: Color::WHITE,
                             },
@@ -437,38 +773,428 @@ fn make_settings(
                     }
                 });
 
+            // enemy difficulty
             parent
-                .spawn((
-                    ButtonBundle {
-                        style: button_style.button.clone(),
-                        background_color: BUTTON_NORMAL_COLOR.into(),
+                .spawn(NodeBundle {
+                    style: Style {
+                        size: Size::new(Val::Percent(100.0), Val::Px(40.0)),
+                        align_items: AlignItems::Center,
                         ..Default::default()
                     },
-                    ButtonAction::Back,
-                ))
+                    background_color: Color::NONE.into(),
+                    ..Default::default()
+                })
                 .with_children(|parent| {
-                    parent.spawn(ImageBundle {
-                        style: button_style.icon.clone(),
-                        image: UiImage(asset_server.load(EXIT_ICON)),
-                        ..Default::default()
-                    });
                     parent.spawn(TextBundle {
-                        text: Text::from_section("Back", button_style.text.clone()),
+                        style: Style {
+                            position: UiRect {
+                                left: Val::Percent(10.0),
+                                ..Default::default()
+                            },
+                            margin: UiRect {
+                                right: Val::Percent(10.0),
+                                ..Default::default()
+                            },
+                            ..Default::default()
+                        },
+                        text: Text::from_section(
+                            "Enemy",
+                            TextStyle {
+                                font: fonts.get("karmatic"),
+                                font_size: 20.0,
+                                color: Color::WHITE,
+                            },
+                        )
+                        .with_alignment(TextAlignment {
+                            horizontal: HorizontalAlign::Center,
+                            ..Default::default()
+                        }),
                         ..Default::default()
                     });
+                    for difficulty_setting in Difficulty::ALL {
+                        parent.spawn((
+                            ButtonBundle {
+                                style: Style {
+                                    size: Size::new(Val::Px(20.0), Val::Px(20.0)),
+                                    margin: UiRect {
+                                        left: Val::Px(2.0),
+                                        right: Val::Px(2.0),
+                                        ..Default::default()
+                                    },
+                                    ..button_style.button.clone()
+                                },
+                                background_color: SETTING_NORMAL_COLOR.into(),
+                                ..Default::default()
+                            },
+                            ValueAction::Difficulty(difficulty_setting),
+                        ));
+                    }
+                    parent.spawn((
+                        TextBundle {
+                            style: Style {
+                                margin: UiRect {
+                                    left: Val::Px(10.0),
+                                    ..Default::default()
+                                },
+                                ..Default::default()
+                            },
+                            text: Text::from_section(
+                                difficulty.name(),
+                                TextStyle {
+                                    font: fonts.get("karmatic"),
+                                    font_size: 20.0,
+                                    color: Color::GOLD,
+                                },
+                            ),
+                            ..Default::default()
+                        },
+                        DifficultyText,
+                    ));
                 });
-        });
-}
-
-fn enter_score(mut time_scale: ResMut<TimeScale>) {
-    time_scale.reset();
-}
 
-fn make_score(
+            // player assist
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        size: Size::new(Val::Percent(100.0), Val::Px(40.0)),
+                        align_items: AlignItems::Center,
+                        ..Default::default()
+                    },
+                    background_color: Color::NONE.into(),
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    parent.spawn(TextBundle {
+                        style: Style {
+                            position: UiRect {
+                                left: Val::Percent(10.0),
+                                ..Default::default()
+                            },
+                            margin: UiRect {
+                                right: Val::Percent(10.0),
+                                ..Default::default()
+                            },
+                            ..Default::default()
+                        },
+                        text: Text::from_section(
+                            "Assist",
+                            TextStyle {
+                                font: fonts.get("karmatic"),
+                                font_size: 20.0,
+                                color: Color::WHITE,
+                            },
+                        )
+                        .with_alignment(TextAlignment {
+                            horizontal: HorizontalAlign::Center,
+                            ..Default::default()
+                        }),
+                        ..Default::default()
+                    });
+                    for assist_setting in [false, true] {
+                        parent.spawn((
+                            ButtonBundle {
+                                style: Style {
+                                    size: Size::new(Val::Px(20.0), Val::Px(20.0)),
+                                    margin: UiRect {
+                                        left: Val::Px(2.0),
+                                        right: Val::Px(2.0),
+                                        ..Default::default()
+                                    },
+                                    ..button_style.button.clone()
+                                },
+                                background_color: SETTING_NORMAL_COLOR.into(),
+                                ..Default::default()
+                            },
+                            ValueAction::Assist(assist_setting),
+                        ));
+                    }
+                    parent.spawn((
+                        TextBundle {
+                            style: Style {
+                                margin: UiRect {
+                                    left: Val::Px(10.0),
+                                    ..Default::default()
+                                },
+                                ..Default::default()
+                            },
+                            text: Text::from_section(
+                                switch_name(assist.enabled),
+                                TextStyle {
+                                    font: fonts.get("karmatic"),
+                                    font_size: 20.0,
+                                    color: Color::GOLD,
+                                },
+                            ),
+                            ..Default::default()
+                        },
+                        AssistText,
+                    ));
+                });
+
+            // extra balls
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        size: Size::new(Val::Percent(100.0), Val::Px(40.0)),
+                        align_items: AlignItems::Center,
+                        ..Default::default()
+                    },
+                    background_color: Color::NONE.into(),
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    parent.spawn(TextBundle {
+                        style: Style {
+                            position: UiRect {
+                                left: Val::Percent(10.0),
+                                ..Default::default()
+                            },
+                            margin: UiRect {
+                                right: Val::Percent(10.0),
+                                ..Default::default()
+                            },
+                            ..Default::default()
+                        },
+                        text: Text::from_section(
+                            "Multi-ball",
+                            TextStyle {
+                                font: fonts.get("karmatic"),
+                                font_size: 20.0,
+                                color: Color::WHITE,
+                            },
+                        )
+                        .with_alignment(TextAlignment {
+                            horizontal: HorizontalAlign::Center,
+                            ..Default::default()
+                        }),
+                        ..Default::default()
+                    });
+                    for multi_ball_setting in [false, true] {
+                        parent.spawn((
+                            ButtonBundle {
+                                style: Style {
+                                    size: Size::new(Val::Px(20.0), Val::Px(20.0)),
+                                    margin: UiRect {
+                                        left: Val::Px(2.0),
+                                        right: Val::Px(2.0),
+                                        ..Default::default()
+                                    },
+                                    ..button_style.button.clone()
+                                },
+                                background_color: SETTING_NORMAL_COLOR.into(),
+                                ..Default::default()
+                            },
+                            ValueAction::MultiBall(multi_ball_setting),
+                        ));
+                    }
+                    parent.spawn((
+                        TextBundle {
+                            style: Style {
+                                margin: UiRect {
+                                    left: Val::Px(10.0),
+                                    ..Default::default()
+                                },
+                                ..Default::default()
+                            },
+                            text: Text::from_section(
+                                switch_name(multi_ball.enabled),
+                                TextStyle {
+                                    font: fonts.get("karmatic"),
+                                    font_size: 20.0,
+                                    color: Color::GOLD,
+                                },
+                            ),
+                            ..Default::default()
+                        },
+                        MultiBallText,
+                    ));
+                });
+
+            // fullscreen
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        size: Size::new(Val::Percent(100.0), Val::Px(40.0)),
+                        align_items: AlignItems::Center,
+                        ..Default::default()
+                    },
+                    background_color: Color::NONE.into(),
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    parent.spawn(TextBundle {
+                        style: Style {
+                            position: UiRect {
+                                left: Val::Percent(10.0),
+                                ..Default::default()
+                            },
+                            margin: UiRect {
+                                right: Val::Percent(10.0),
+                                ..Default::default()
+                            },
+                            ..Default::default()
+                        },
+                        text: Text::from_section(
+                            "Fullscreen",
+                            TextStyle {
+                                font: fonts.get("karmatic"),
+                                font_size: 20.0,
+                                color: Color::WHITE,
+                            },
+                        )
+                        .with_alignment(TextAlignment {
+                            horizontal: HorizontalAlign::Center,
+                            ..Default::default()
+                        }),
+                        ..Default::default()
+                    });
+                    for fullscreen in [false, true] {
+                        parent.spawn((
+                            ButtonBundle {
+                                style: Style {
+                                    size: Size::new(Val::Px(20.0), Val::Px(20.0)),
+                                    margin: UiRect {
+                                        left: Val::Px(2.0),
+                                        right: Val::Px(2.0),
+                                        ..Default::default()
+                                    },
+                                    ..button_style.button.clone()
+                                },
+                                background_color: SETTING_NORMAL_COLOR.into(),
+                                ..Default::default()
+                            },
+                            ValueAction::Fullscreen(fullscreen),
+                        ));
+                    }
+                    parent.spawn((
+                        TextBundle {
+                            style: Style {
+                                margin: UiRect {
+                                    left: Val::Px(10.0),
+                                    ..Default::default()
+                                },
+                                ..Default::default()
+                            },
+                            text: Text::from_section(
+                                switch_name(display.fullscreen),
+                                TextStyle {
+                                    font: fonts.get("karmatic"),
+                                    font_size: 20.0,
+                                    color: Color::GOLD,
+                                },
+                            ),
+                            ..Default::default()
+                        },
+                        FullscreenText,
+                    ));
+                });
+
+            // controls
+            for action in Action::ALL {
+                parent
+                    .spawn(NodeBundle {
+                        style: Style {
+                            size: Size::new(Val::Percent(100.0), Val::Px(24.0)),
+                            align_items: AlignItems::Center,
+                            ..Default::default()
+                        },
+                        background_color: Color::NONE.into(),
+                        ..Default::default()
+                    })
+                    .with_children(|parent| {
+                        parent.spawn(TextBundle {
+                            style: Style {
+                                size: Size::new(Val::Px(80.0), Val::Auto),
+                                position: UiRect {
+                                    left: Val::Percent(10.0),
+                                    ..Default::default()
+                                },
+                                margin: UiRect {
+                                    right: Val::Percent(10.0),
+                                    ..Default::default()
+                                },
+                                ..Default::default()
+                            },
+                            text: Text::from_section(
+                                action.name(),
+                                TextStyle {
+                                    font: fonts.get("karmatic"),
+                                    font_size: 15.0,
+                                    color: Color::WHITE,
+                                },
+                            ),
+                            ..Default::default()
+                        });
+                        parent
+                            .spawn((
+                                ButtonBundle {
+                                    style: Style {
+                                        size: Size::new(Val::Px(240.0), Val::Px(20.0)),
+                                        margin: UiRect::all(Val::Px(2.0)),
+                                        justify_content: JustifyContent::FlexStart,
+                                        align_items: AlignItems::Center,
+                                        ..Default::default()
+                                    },
+                                    background_color: BUTTON_NORMAL_COLOR.into(),
+                                    ..Default::default()
+                                },
+                                ButtonAction::Rebind(action),
+                            ))
+                            .with_children(|parent| {
+                                parent.spawn((
+                                    TextBundle {
+                                        text: Text::from_section(
+                                            binding_names(&input_map, action),
+                                            TextStyle {
+                                                font_size: 15.0,
+                                                ..button_style.text.clone()
+                                            },
+                                        ),
+                                        ..Default::default()
+                                    },
+                                    BindingText(action),
+                                ));
+                            });
+                    });
+            }
+
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: button_style.button.clone(),
+                        background_color: BUTTON_NORMAL_COLOR.into(),
+                        ..Default::default()
+                    },
+                    ButtonAction::Back,
+                ))
+                .with_children(|parent| {
+                    parent.spawn(ImageBundle {
+                        style: button_style.icon.clone(),
+                        image: UiImage(images.get("exit_icon")),
+                        ..Default::default()
+                    });
+                    parent.spawn(TextBundle {
+                        text: Text::from_section("Back", button_style.text.clone()),
+                        ..Default::default()
+                    });
+                });
+        });
+}
+
+fn enter_score(mut time_scale: ResMut<TimeScale>) {
+    time_scale.reset();
+}
+
+#[allow(clippy::too_many_arguments)]
+fn make_score(
     mut commands: Commands,
-    time: Res<Time>,
+    clock: Res<GameClock>,
     score: Res<Score>,
-    asset_server: Res<AssetServer>,
+    rng: Res<GameRng>,
+    difficulty: Res<Difficulty>,
+    records: Res<Records>,
+    fonts: Res<AssetMap<Font>>,
+    images: Res<AssetMap<Image>>,
     button_style: Res<ButtonStyle>,
 ) {
     commands
@@ -502,7 +1228,7 @@ fn make_score(
                     text: Text::from_section(
                         "You Win!",
                         TextStyle {
-                            font: asset_server.load(FONT_ARCADE),
+                            font: fonts.get("arcade"),
                             font_size: 50.0,
                             color: Color::WHITE,
                         },
@@ -530,24 +1256,23 @@ fn make_score(
                 ..Default::default()
             };
 
-            // time
-            let time_passed = time.elapsed_seconds() - score.timestamp;
+            // difficulty
             parent.spawn(TextBundle {
                 style: term_style.clone(),
                 text: Text {
                     sections: vec![
                         TextSection {
-                            value: "Time: ".into(),
+                            value: "Difficulty: ".into(),
                             style: TextStyle {
-                                font: asset_server.load(FONT_KARMATIC),
+                                font: fonts.get("karmatic"),
                                 font_size: 20.0,
                                 color: Color::WHITE,
                             },
                         },
                         TextSection {
-                            value: format!("{time_passed:.2}"),
+                            value: difficulty.name().into(),
                             style: TextStyle {
-                                font: asset_server.load(FONT_KARMATIC),
+                                font: fonts.get("karmatic"),
                                 font_size: 20.0,
                                 color: Color::GOLD,
                             },
@@ -558,23 +1283,24 @@ fn make_score(
                 ..Default::default()
             });
 
-            // player hits
+            // time
+            let time_passed = score.time(&clock);
             parent.spawn(TextBundle {
                 style: term_style.clone(),
                 text: Text {
                     sections: vec![
                         TextSection {
-                            value: "Hits: ".into(),
+                            value: "Time: ".into(),
                             style: TextStyle {
-                                font: asset_server.load(FONT_KARMATIC),
+                                font: fonts.get("karmatic"),
                                 font_size: 20.0,
                                 color: Color::WHITE,
                             },
                         },
                         TextSection {
-                            value: score.hits.to_string(),
+                            value: format!("{time_passed:.2}"),
                             style: TextStyle {
-                                font: asset_server.load(FONT_KARMATIC),
+                                font: fonts.get("karmatic"),
                                 font_size: 20.0,
                                 color: Color::GOLD,
                             },
@@ -585,23 +1311,23 @@ fn make_score(
                 ..Default::default()
             });
 
-            // player miss
+            // composite score, ranking the high score table
             parent.spawn(TextBundle {
-                style: term_style,
+                style: term_style.clone(),
                 text: Text {
                     sections: vec![
                         TextSection {
-                            value: "Miss: ".into(),
+                            value: "Points: ".into(),
                             style: TextStyle {
-                                font: asset_server.load(FONT_KARMATIC),
+                                font: fonts.get("karmatic"),
                                 font_size: 20.0,
                                 color: Color::WHITE,
                             },
                         },
                         TextSection {
-                            value: score.miss.to_string(),
+                            value: score.points(time_passed).to_string(),
                             style: TextStyle {
-                                font: asset_server.load(FONT_KARMATIC),
+                                font: fonts.get("karmatic"),
                                 font_size: 20.0,
                                 color: Color::GOLD,
                             },
@@ -612,28 +1338,374 @@ fn make_score(
                 ..Default::default()
             });
 
-            parent
-                .spawn((
-                    ButtonBundle {
-                        style: button_style.button.clone(),
-                        background_color: BUTTON_NORMAL_COLOR.into(),
-                        ..Default::default()
-                    },
-                    ButtonAction::Back,
-                ))
-                .with_children(|parent| {
-                    parent.spawn(ImageBundle {
-                        style: button_style.icon.clone(),
-                        image: UiImage(asset_server.load(EXIT_ICON)),
+            // initials of a new record
+            if let Some(latest) = records.latest {
+                parent
+                    .spawn(NodeBundle {
+                        style: term_style.clone(),
+                        background_color: Color::NONE.into(),
                         ..Default::default()
-                    });
-                    parent.spawn(TextBundle {
-                        text: Text::from_section("Back", button_style.text.clone()),
-                        ..Default::default()
-                    });
-                });
-        });
-}
+                    })
+                    .with_children(|parent| {
+                        parent.spawn((
+                            TextBundle {
+                                text: Text::from_sections(
+                                    std::iter::once("Name: ")
+                                        .chain(["A"; RECORD_INITIALS_LENGTH])
+                                        .map(|value| {
+                                            TextSection::new(
+                                                value,
+                                                TextStyle {
+                                                    font: fonts.get("karmatic"),
+                                                    font_size: 20.0,
+                                                    color: Color::WHITE,
+                                                },
+                                            )
+                                        }),
+                                ),
+                                ..Default::default()
+                            },
+                            InitialsText,
+                        ));
+                        parent.spawn((
+                            TextBundle {
+                                style: Style {
+                                    margin: UiRect {
+                                        left: Val::Px(20.0),
+                                        ..Default::default()
+                                    },
+                                    ..Default::default()
+                                },
+                                text: Text::from_section(
+                                    format!("New Record! #{}", latest.rank + 1),
+                                    TextStyle {
+                                        font: fonts.get("karmatic"),
+                                        font_size: 20.0,
+                                        color: Color::GOLD,
+                                    },
+                                ),
+                                ..Default::default()
+                            },
+                            ColorText::new(FLIP_TEXT_COLORS.into(), 30.0 / MENU_MUSIC_BPM),
+                        ));
+                    });
+            }
+
+            // player hits
+            parent.spawn(TextBundle {
+                style: term_style.clone(),
+                text: Text {
+                    sections: vec![
+                        TextSection {
+                            value: "Hits: ".into(),
+                            style: TextStyle {
+                                font: fonts.get("karmatic"),
+                                font_size: 20.0,
+                                color: Color::WHITE,
+                            },
+                        },
+                        TextSection {
+                            value: score.hits.to_string(),
+                            style: TextStyle {
+                                font: fonts.get("karmatic"),
+                                font_size: 20.0,
+                                color: Color::GOLD,
+                            },
+                        },
+                    ],
+                    ..Default::default()
+                },
+                ..Default::default()
+            });
+
+            // player miss
+            parent.spawn(TextBundle {
+                style: term_style.clone(),
+                text: Text {
+                    sections: vec![
+                        TextSection {
+                            value: "Miss: ".into(),
+                            style: TextStyle {
+                                font: fonts.get("karmatic"),
+                                font_size: 20.0,
+                                color: Color::WHITE,
+                            },
+                        },
+                        TextSection {
+                            value: score.miss.to_string(),
+                            style: TextStyle {
+                                font: fonts.get("karmatic"),
+                                font_size: 20.0,
+                                color: Color::GOLD,
+                            },
+                        },
+                    ],
+                    ..Default::default()
+                },
+                ..Default::default()
+            });
+
+            // random seed, to replay the run
+            parent.spawn(TextBundle {
+                style: term_style,
+                text: Text {
+                    sections: vec![
+                        TextSection {
+                            value: "Seed: ".into(),
+                            style: TextStyle {
+                                font: fonts.get("karmatic"),
+                                font_size: 20.0,
+                                color: Color::WHITE,
+                            },
+                        },
+                        TextSection {
+                            value: rng.seed().to_string(),
+                            style: TextStyle {
+                                font: fonts.get("karmatic"),
+                                font_size: 20.0,
+                                color: Color::GOLD,
+                            },
+                        },
+                    ],
+                    ..Default::default()
+                },
+                ..Default::default()
+            });
+
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: button_style.button.clone(),
+                        background_color: BUTTON_NORMAL_COLOR.into(),
+                        ..Default::default()
+                    },
+                    ButtonAction::Back,
+                ))
+                .with_children(|parent| {
+                    parent.spawn(ImageBundle {
+                        style: button_style.icon.clone(),
+                        image: UiImage(images.get("exit_icon")),
+                        ..Default::default()
+                    });
+                    parent.spawn(TextBundle {
+                        text: Text::from_section("Back", button_style.text.clone()),
+                        ..Default::default()
+                    });
+                });
+        });
+}
+
+fn make_winner(
+    mut commands: Commands,
+    clock: Res<GameClock>,
+    score: Res<Score>,
+    winner: Res<Winner>,
+    fonts: Res<AssetMap<Font>>,
+    images: Res<AssetMap<Image>>,
+    button_style: Res<ButtonStyle>,
+) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    ..Default::default()
+                },
+                background_color: Color::NONE.into(),
+                ..Default::default()
+            },
+            Cleanup,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle {
+                    style: Style {
+                        position: UiRect {
+                            left: Val::Percent(10.0),
+                            ..Default::default()
+                        },
+                        margin: UiRect {
+                            bottom: Val::Percent(20.0),
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    },
+                    text: Text::from_section(
+                        format!("{} Wins!", winner.name()),
+                        TextStyle {
+                            font: fonts.get("arcade"),
+                            font_size: 50.0,
+                            color: Color::WHITE,
+                        },
+                    )
+                    .with_alignment(TextAlignment {
+                        horizontal: HorizontalAlign::Center,
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                },
+                ColorText::new(FLIP_TEXT_COLORS.into(), 30.0 / MENU_MUSIC_BPM),
+            ));
+
+            // time
+            parent.spawn(TextBundle {
+                style: Style {
+                    size: Size::new(Val::Percent(100.0), Val::Px(30.0)),
+                    position: UiRect {
+                        left: Val::Percent(10.0),
+                        ..Default::default()
+                    },
+                    margin: UiRect {
+                        top: Val::Px(10.0),
+                        bottom: Val::Px(10.0),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                text: Text {
+                    sections: vec![
+                        TextSection {
+                            value: "Time: ".into(),
+                            style: TextStyle {
+                                font: fonts.get("karmatic"),
+                                font_size: 20.0,
+                                color: Color::WHITE,
+                            },
+                        },
+                        TextSection {
+                            value: format!("{:.2}", score.time(&clock)),
+                            style: TextStyle {
+                                font: fonts.get("karmatic"),
+                                font_size: 20.0,
+                                color: Color::GOLD,
+                            },
+                        },
+                    ],
+                    ..Default::default()
+                },
+                ..Default::default()
+            });
+
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: button_style.button.clone(),
+                        background_color: BUTTON_NORMAL_COLOR.into(),
+                        ..Default::default()
+                    },
+                    ButtonAction::Play,
+                ))
+                .with_children(|parent| {
+                    parent.spawn(ImageBundle {
+                        style: button_style.icon.clone(),
+                        image: UiImage(images.get("right_icon")),
+                        ..Default::default()
+                    });
+                    parent.spawn(TextBundle {
+                        text: Text::from_section("Rematch", button_style.text.clone()),
+                        ..Default::default()
+                    });
+                });
+
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: button_style.button.clone(),
+                        background_color: BUTTON_NORMAL_COLOR.into(),
+                        ..Default::default()
+                    },
+                    ButtonAction::Back,
+                ))
+                .with_children(|parent| {
+                    parent.spawn(ImageBundle {
+                        style: button_style.icon.clone(),
+                        image: UiImage(images.get("exit_icon")),
+                        ..Default::default()
+                    });
+                    parent.spawn(TextBundle {
+                        text: Text::from_section("Back", button_style.text.clone()),
+                        ..Default::default()
+                    });
+                });
+        });
+}
+
+/// Moves [`MenuFocus`] to the nearest menu item in the pressed direction.
+#[allow(clippy::type_complexity)]
+fn navigate_menu(
+    input: Res<ControlInput>,
+    mut focus: ResMut<MenuFocus>,
+    item_query: Query<
+        (
+            Entity,
+            &GlobalTransform,
+            &Interaction,
+            ChangeTrackers<Interaction>,
+        ),
+        (With<Button>, Or<(With<ButtonAction>, With<ValueAction>)>),
+    >,
+    app_state: Res<State<AppState>>,
+    records: Res<Records>,
+) {
+    // the mouse takes over as soon as it touches an item
+    if item_query.iter().any(|(_, _, interaction, tracker)| {
+        tracker.is_changed() && *interaction != Interaction::None
+    }) {
+        if focus.0.is_some() {
+            focus.0 = None;
+        }
+        return;
+    }
+
+    let position = |entity| {
+        item_query
+            .get(entity)
+            .ok()
+            .map(|(_, transform, ..)| transform.translation().truncate())
+    };
+    let items = item_query
+        .iter()
+        .map(|(entity, transform, ..)| (entity, transform.translation().truncate()));
+
+    // while a new record is signed, the directions edit its initials,
+    // and the focus stays on the only button so that confirming leaves the score screen
+    let signing = *app_state.current() == AppState::Score && records.latest.is_some();
+
+    // UI coordinates grow downwards
+    let direction = if signing {
+        Vec2::ZERO
+    } else {
+        Vec2::new(input.navigate.x as f32, -input.navigate.y as f32)
+    };
+    let next = match focus.0.and_then(position) {
+        Some(_) if direction == Vec2::ZERO => None,
+        Some(current) => items
+            .filter(|(_, position)| (*position - current).dot(direction) > 0.0)
+            .min_by(|(_, a), (_, b)| {
+                // prefer items straight ahead over the ones to the side
+                let cost = |position: Vec2| {
+                    let delta = position - current;
+                    let along = delta.dot(direction.normalize());
+                    along + 2.0 * (delta - along * direction.normalize()).length()
+                };
+                cost(*a).total_cmp(&cost(*b))
+            })
+            .map(|(entity, _)| entity),
+        // start from the top item, or keep the focus across screens
+        None if direction != Vec2::ZERO || focus.0.is_some() || signing => items
+            .min_by(|(_, a), (_, b)| a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x)))
+            .map(|(entity, _)| entity),
+        None => None,
+    };
+
+    if let Some(next) = next {
+        if focus.0 != Some(next) {
+            focus.0 = Some(next);
+        }
+    }
+}
 
 #[allow(clippy::type_complexity)]
 fn button_audio(
@@ -643,21 +1715,38 @@ fn button_audio(
     >,
     audio: Res<AudioChannel<ButtonAudio>>,
     volume: Res<AudioVolume>,
-    asset_server: Res<AssetServer>,
+    audios: Res<AssetMap<AudioSource>>,
+    input: Res<ControlInput>,
+    focus: Res<MenuFocus>,
+    button_query: Query<(), With<Button>>,
 ) {
+    if focus.0.is_some_and(|entity| button_query.contains(entity)) {
+        let volume = volume.effects * 0.5;
+        if input.confirm {
+            audio.set_volume(volume.into());
+            audio.play(audios.get("button_click"));
+        } else if focus.is_changed() {
+            audio.set_volume(volume.into());
+            audio.play(audios.get("button_hover"));
+        }
+    }
+
     // let channel = AudioChannel::new("button".into());
     for (interaction, maybe_action) in interaction_query.iter() {
         match *interaction {
             Interaction::Clicked => {
-                let volume = volume.effects * 0.5;
-                audio.set_volume(volume.into());
-                audio.play(asset_server.load(BUTTON_CLICK_AUDIO));
+                // the retry button of the loading screen is clicked before the sounds are loaded
+                if let Some(click) = audios.try_get("button_click") {
+                    let volume = volume.effects * 0.5;
+                    audio.set_volume(volume.into());
+                    audio.play(click);
+                }
             }
             Interaction::Hovered => {
                 if maybe_action.is_some() {
                     let volume = volume.effects * 0.5;
                     audio.set_volume(volume.into());
-                    audio.play(asset_server.load(BUTTON_HOVER_AUDIO));
+                    audio.play(audios.get("button_hover"));
                 }
             }
             Interaction::None => {}
@@ -667,17 +1756,29 @@ fn button_audio(
 
 #[allow(clippy::type_complexity)]
 fn button_system(
+    focus: Res<MenuFocus>,
     mut interaction_query: Query<
-        (&Interaction, &mut BackgroundColor, &Children),
-        (Changed<Interaction>, With<Button>, With<ButtonAction>),
+        (
+            Entity,
+            &Interaction,
+            ChangeTrackers<Interaction>,
+            &mut BackgroundColor,
+            &Children,
+        ),
+        (With<Button>, With<ButtonAction>),
     >,
     mut text_query: Query<&mut Text>,
 ) {
-    for (interaction, mut color, children) in interaction_query.iter_mut() {
+    for (entity, interaction, tracker, mut color, children) in interaction_query.iter_mut() {
+        if !tracker.is_changed() && !focus.is_changed() {
+            continue;
+        }
+
+        let interaction = focus.interaction(entity, interaction);
         for child in children.iter() {
             if let Ok(mut text) = text_query.get_mut(*child) {
                 let text_color = &mut text.sections[0].style.color;
-                match *interaction {
+                match interaction {
                     Interaction::Clicked => {
                         *text_color = BUTTON_TEXT_PRESSED_COLOR;
                         *color = BUTTON_PRESSED_COLOR.into();
@@ -696,31 +1797,90 @@ fn button_system(
     }
 }
 
-#[allow(clippy::type_complexity)]
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
 fn button_action(
-    interaction_query: Query<(&Interaction, &ButtonAction), (Changed<Interaction>, With<Button>)>,
+    input: Res<ControlInput>,
+    focus: Res<MenuFocus>,
+    interaction_query: Query<
+        (
+            Entity,
+            &Interaction,
+            ChangeTrackers<Interaction>,
+            &ButtonAction,
+        ),
+        With<Button>,
+    >,
     mut app_state: ResMut<State<AppState>>,
+    mut replays: ResMut<Replays>,
+    mut rng: ResMut<GameRng>,
+    mut run_settings: RunSettingsParam,
+    mut rebinding: ResMut<Rebinding>,
+    game_mode: Res<GameMode>,
 ) {
-    for (interaction, action) in interaction_query.iter() {
-        if *interaction == Interaction::Clicked {
+    for (entity, interaction, tracker, action) in interaction_query.iter() {
+        if (tracker.is_changed() && *interaction == Interaction::Clicked)
+            || focus.confirmed(entity, &input)
+        {
             let state = match action {
-                ButtonAction::Play => AppState::Battle,
+                ButtonAction::Play => game_mode.state(),
+                ButtonAction::Replay => AppState::Replays,
+                ButtonAction::PlayReplay(index) => {
+                    match replays.play(*index, &mut rng, &mut run_settings) {
+                        Some(state) => state,
+                        None => continue,
+                    }
+                }
                 ButtonAction::Tutorial => AppState::Practice,
+                // the settings opened from the pause menu return to it
+                ButtonAction::Settings if *app_state.current() == AppState::Paused => {
+                    app_state.push(AppState::Settings).unwrap();
+                    continue;
+                }
                 ButtonAction::Settings => AppState::Settings,
-                ButtonAction::Back => AppState::Menu,
+                ButtonAction::History => AppState::History,
+                ButtonAction::Rebind(action) => {
+                    rebinding.action = Some(*action);
+                    rebinding.conflict = None;
+                    continue;
+                }
+                ButtonAction::Back | ButtonAction::Resume if !app_state.inactives().is_empty() => {
+                    app_state.pop().unwrap();
+                    continue;
+                }
+                ButtonAction::Back | ButtonAction::Resume => AppState::Menu,
+                // leaves the paused run and starts over in the same mode
+                ButtonAction::Restart => {
+                    let run = app_state.inactives()[0].clone();
+                    app_state.replace(run).unwrap();
+                    continue;
+                }
+                ButtonAction::Quit => {
+                    app_state.replace(AppState::Menu).unwrap();
+                    continue;
+                }
             };
             app_state.set(state).unwrap();
         }
     }
 }
 
-#[allow(clippy::type_complexity)]
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
 fn value_system(
-    mut interaction_query: Query<(&Interaction, &mut BackgroundColor, &ValueAction), With<Button>>,
+    focus: Res<MenuFocus>,
+    mut interaction_query: Query<
+        (Entity, &Interaction, &mut BackgroundColor, &ValueAction),
+        With<Button>,
+    >,
     volume: Res<AudioVolume>,
+    difficulty: Res<Difficulty>,
+    game_mode: Res<GameMode>,
+    assist: Res<Assist>,
+    multi_ball: Res<MultiBall>,
+    display: Res<DisplaySettings>,
+    history: Option<Res<HistoryDifficulty>>,
 ) {
-    for (interaction, mut color, action) in interaction_query.iter_mut() {
-        match *interaction {
+    for (entity, interaction, mut color, action) in interaction_query.iter_mut() {
+        match focus.interaction(entity, interaction) {
             Interaction::Hovered => *color = SETTING_HOVERED_COLOR.into(),
             _ => {
                 *color = SETTING_NORMAL_COLOR.into();
@@ -735,27 +1895,538 @@ fn value_system(
                             *color = SETTING_ACTIVE_COLOR.into();
                         }
                     }
+                    ValueAction::Difficulty(v) => {
+                        if *difficulty >= *v {
+                            *color = SETTING_ACTIVE_COLOR.into();
+                        }
+                    }
+                    ValueAction::Mode(v) => {
+                        if *game_mode >= *v {
+                            *color = SETTING_ACTIVE_COLOR.into();
+                        }
+                    }
+                    ValueAction::Assist(v) => {
+                        if assist.enabled >= *v {
+                            *color = SETTING_ACTIVE_COLOR.into();
+                        }
+                    }
+                    ValueAction::MultiBall(v) => {
+                        if multi_ball.enabled >= *v {
+                            *color = SETTING_ACTIVE_COLOR.into();
+                        }
+                    }
+                    ValueAction::Fullscreen(v) => {
+                        if display.fullscreen >= *v {
+                            *color = SETTING_ACTIVE_COLOR.into();
+                        }
+                    }
+                    ValueAction::History(v) => {
+                        if history.as_ref().is_some_and(|history| history.0 >= *v) {
+                            *color = SETTING_ACTIVE_COLOR.into();
+                        }
+                    }
                 };
             }
         }
     }
 }
 
-#[allow(clippy::type_complexity)]
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
 fn value_action(
-    interaction_query: Query<(&Interaction, &ValueAction), (Changed<Interaction>, With<Button>)>,
+    mut commands: Commands,
+    input: Res<ControlInput>,
+    focus: Res<MenuFocus>,
+    interaction_query: Query<
+        (
+            Entity,
+            &Interaction,
+            ChangeTrackers<Interaction>,
+            &ValueAction,
+        ),
+        With<Button>,
+    >,
     mut volume: ResMut<AudioVolume>,
+    mut difficulty: ResMut<Difficulty>,
+    mut game_mode: ResMut<GameMode>,
+    mut assist: ResMut<Assist>,
+    mut multi_ball: ResMut<MultiBall>,
+    mut display: ResMut<DisplaySettings>,
+    mut text_queries: ParamSet<(
+        Query<&mut Text, With<DifficultyText>>,
+        Query<&mut Text, With<ModeText>>,
+        Query<&mut Text, With<AssistText>>,
+        Query<&mut Text, With<MultiBallText>>,
+        Query<&mut Text, With<FullscreenText>>,
+    )>,
     audio: Res<Audio>,
 ) {
-    for (interaction, action) in interaction_query.iter() {
-        if *interaction == Interaction::Clicked {
+    for (entity, interaction, tracker, action) in interaction_query.iter() {
+        if (tracker.is_changed() && *interaction == Interaction::Clicked)
+            || focus.confirmed(entity, &input)
+        {
             match action {
                 ValueAction::AudioVolume(v) => volume.effects = *v,
                 ValueAction::MusicVolume(v) => {
                     volume.music = *v;
                     audio.set_volume(volume.music.into());
                 }
+                ValueAction::Difficulty(v) => {
+                    *difficulty = *v;
+                    for mut text in text_queries.p0().iter_mut() {
+                        text.sections[0].value = difficulty.name().into();
+                    }
+                }
+                ValueAction::Mode(v) => {
+                    *game_mode = *v;
+                    for mut text in text_queries.p1().iter_mut() {
+                        text.sections[0].value = game_mode.name().into();
+                    }
+                }
+                ValueAction::History(v) => commands.insert_resource(HistoryDifficulty(*v)),
+                ValueAction::Assist(v) => {
+                    assist.enabled = *v;
+                    for mut text in text_queries.p2().iter_mut() {
+                        text.sections[0].value = switch_name(assist.enabled).into();
+                    }
+                }
+                ValueAction::MultiBall(v) => {
+                    multi_ball.enabled = *v;
+                    for mut text in text_queries.p3().iter_mut() {
+                        text.sections[0].value = switch_name(multi_ball.enabled).into();
+                    }
+                }
+                ValueAction::Fullscreen(v) => {
+                    display.fullscreen = *v;
+                    for mut text in text_queries.p4().iter_mut() {
+                        text.sections[0].value = switch_name(display.fullscreen).into();
+                    }
+                }
             }
         }
     }
 }
+
+fn switch_name(enabled: bool) -> &'static str {
+    if enabled {
+        "On"
+    } else {
+        "Off"
+    }
+}
+
+fn binding_names(input_map: &InputMap, action: Action) -> String {
+    input_map
+        .bindings(action)
+        .iter()
+        .map(|binding| binding.name())
+        .collect::<Vec<_>>()
+        .join(" / ")
+}
+
+fn binding_text(
+    input_map: Res<InputMap>,
+    rebinding: Res<Rebinding>,
+    mut text_query: Query<(&mut Text, &BindingText)>,
+) {
+    if !input_map.is_changed() && !rebinding.is_changed() {
+        return;
+    }
+
+    for (mut text, BindingText(action)) in text_query.iter_mut() {
+        text.sections[0].value = if rebinding.action != Some(*action) {
+            binding_names(&input_map, *action)
+        } else if let Some(conflict) = rebinding.conflict {
+            format!("Used by {}, try another...", conflict.name())
+        } else {
+            "Press a key or button...".into()
+        };
+    }
+}
+
+fn cancel_rebinding(mut rebinding: ResMut<Rebinding>) {
+    rebinding.action = None;
+    rebinding.conflict = None;
+}
+
+/// Shows the initials of the new record, with the letter being edited in gold.
+fn initials_text(
+    records: Res<Records>,
+    mut text_query: Query<&mut Text, With<InitialsText>>,
+    added_query: Query<(), Added<InitialsText>>,
+) {
+    let (Some(latest), Some(record)) = (records.latest, records.latest()) else {
+        return;
+    };
+    if !records.is_changed() && added_query.is_empty() {
+        return;
+    }
+
+    for mut text in text_query.iter_mut() {
+        for (index, letter) in record.initials.chars().enumerate() {
+            if let Some(section) = text.sections.get_mut(index + 1) {
+                section.value = letter.to_string();
+                section.style.color = if index == latest.cursor {
+                    Color::GOLD
+                } else {
+                    Color::WHITE
+                };
+            }
+        }
+    }
+}
+
+fn enter_history(mut commands: Commands, difficulty: Res<Difficulty>) {
+    commands.insert_resource(HistoryDifficulty(*difficulty));
+}
+
+fn exit_history(mut commands: Commands) {
+    commands.remove_resource::<HistoryDifficulty>();
+}
+
+fn make_history(
+    mut commands: Commands,
+    fonts: Res<AssetMap<Font>>,
+    images: Res<AssetMap<Image>>,
+    button_style: Res<ButtonStyle>,
+) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    ..Default::default()
+                },
+                background_color: Color::NONE.into(),
+                ..Default::default()
+            },
+            Cleanup,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle {
+                style: Style {
+                    position: UiRect {
+                        left: Val::Percent(10.0),
+                        ..Default::default()
+                    },
+                    margin: UiRect {
+                        bottom: Val::Percent(10.0),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                text: Text::from_section(
+                    "History",
+                    TextStyle {
+                        font: fonts.get("karmatic"),
+                        font_size: 30.0,
+                        color: Color::WHITE,
+                    },
+                ),
+                ..Default::default()
+            });
+
+            // difficulty of the table
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        size: Size::new(Val::Percent(100.0), Val::Px(40.0)),
+                        align_items: AlignItems::Center,
+                        ..Default::default()
+                    },
+                    background_color: Color::NONE.into(),
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    parent.spawn(TextBundle {
+                        style: Style {
+                            position: UiRect {
+                                left: Val::Percent(10.0),
+                                ..Default::default()
+                            },
+                            margin: UiRect {
+                                right: Val::Percent(10.0),
+                                ..Default::default()
+                            },
+                            ..Default::default()
+                        },
+                        text: Text::from_section(
+                            "Enemy",
+                            TextStyle {
+                                font: fonts.get("karmatic"),
+                                font_size: 20.0,
+                                color: Color::WHITE,
+                            },
+                        ),
+                        ..Default::default()
+                    });
+                    for difficulty_setting in Difficulty::ALL {
+                        parent.spawn((
+                            ButtonBundle {
+                                style: Style {
+                                    size: Size::new(Val::Px(20.0), Val::Px(20.0)),
+                                    margin: UiRect {
+                                        left: Val::Px(2.0),
+                                        right: Val::Px(2.0),
+                                        ..Default::default()
+                                    },
+                                    ..button_style.button.clone()
+                                },
+                                background_color: SETTING_NORMAL_COLOR.into(),
+                                ..Default::default()
+                            },
+                            ValueAction::History(difficulty_setting),
+                        ));
+                    }
+                    parent.spawn((
+                        TextBundle {
+                            style: Style {
+                                margin: UiRect {
+                                    left: Val::Px(10.0),
+                                    ..Default::default()
+                                },
+                                ..Default::default()
+                            },
+                            text: Text::from_section(
+                                "",
+                                TextStyle {
+                                    font: fonts.get("karmatic"),
+                                    font_size: 20.0,
+                                    color: Color::GOLD,
+                                },
+                            ),
+                            ..Default::default()
+                        },
+                        DifficultyText,
+                    ));
+                });
+
+            // high score table, filled by `record_text`
+            for index in 0..RECORDS_COUNT {
+                parent.spawn((
+                    TextBundle {
+                        style: Style {
+                            size: Size::new(Val::Percent(100.0), Val::Px(22.0)),
+                            position: UiRect {
+                                left: Val::Percent(10.0),
+                                ..Default::default()
+                            },
+                            ..Default::default()
+                        },
+                        text: Text::from_section(
+                            "",
+                            TextStyle {
+                                font: fonts.get("fira_mono"),
+                                font_size: 15.0,
+                                color: Color::WHITE,
+                            },
+                        ),
+                        ..Default::default()
+                    },
+                    RecordText(index),
+                ));
+            }
+
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: button_style.button.clone(),
+                        background_color: BUTTON_NORMAL_COLOR.into(),
+                        ..Default::default()
+                    },
+                    ButtonAction::Back,
+                ))
+                .with_children(|parent| {
+                    parent.spawn(ImageBundle {
+                        style: button_style.icon.clone(),
+                        image: UiImage(images.get("exit_icon")),
+                        ..Default::default()
+                    });
+                    parent.spawn(TextBundle {
+                        text: Text::from_section("Back", button_style.text.clone()),
+                        ..Default::default()
+                    });
+                });
+        });
+}
+
+/// Fills the high score table of the history page, highlighting the latest record.
+fn record_text(
+    records: Res<Records>,
+    history: Option<Res<HistoryDifficulty>>,
+    mut record_query: Query<(&mut Text, &RecordText), Without<DifficultyText>>,
+    mut difficulty_query: Query<&mut Text, With<DifficultyText>>,
+    added_query: Query<(), Added<RecordText>>,
+) {
+    let Some(history) = history else {
+        return;
+    };
+    if !records.is_changed() && !history.is_changed() && added_query.is_empty() {
+        return;
+    }
+
+    let difficulty = history.0;
+    let table = records.table(difficulty);
+    let latest = records
+        .latest
+        .filter(|latest| latest.difficulty == difficulty)
+        .map(|latest| latest.rank);
+
+    for (mut text, RecordText(index)) in record_query.iter_mut() {
+        let section = &mut text.sections[0];
+        section.value = match table.get(*index) {
+            Some(record) => format!(
+                "{:>2}. {:<3} {:>6} {:>7.2}s {:>3} hits",
+                index + 1,
+                record.initials,
+                record.points,
+                record.time,
+                record.hits
+            ),
+            None => format!("{:>2}. ---", index + 1),
+        };
+        section.style.color = if latest == Some(*index) {
+            Color::GOLD
+        } else {
+            Color::WHITE
+        };
+    }
+
+    for mut text in difficulty_query.iter_mut() {
+        text.sections[0].value = difficulty.name().into();
+    }
+}
+
+fn enter_replays(mut replays: ResMut<Replays>) {
+    replays.refresh();
+}
+
+fn make_replays(
+    mut commands: Commands,
+    fonts: Res<AssetMap<Font>>,
+    images: Res<AssetMap<Image>>,
+    button_style: Res<ButtonStyle>,
+    replays: Res<Replays>,
+) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    ..Default::default()
+                },
+                background_color: Color::NONE.into(),
+                ..Default::default()
+            },
+            Cleanup,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle {
+                style: Style {
+                    position: UiRect {
+                        left: Val::Percent(10.0),
+                        ..Default::default()
+                    },
+                    margin: UiRect {
+                        bottom: Val::Percent(10.0),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                text: Text::from_section(
+                    "Replays",
+                    TextStyle {
+                        font: fonts.get("karmatic"),
+                        font_size: 30.0,
+                        color: Color::WHITE,
+                    },
+                ),
+                ..Default::default()
+            });
+
+            if replays.saved().is_empty() {
+                parent.spawn(TextBundle {
+                    style: Style {
+                        position: UiRect {
+                            left: Val::Percent(10.0),
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    },
+                    text: Text::from_section(
+                        "No saved runs yet",
+                        TextStyle {
+                            font: fonts.get("karmatic"),
+                            font_size: 15.0,
+                            color: Color::WHITE,
+                        },
+                    ),
+                    ..Default::default()
+                });
+            }
+
+            // saved runs, newest first
+            for (index, saved) in replays.saved().iter().enumerate() {
+                let mode = match saved.state {
+                    AppState::Practice => "Practice",
+                    _ => "Battle",
+                };
+                parent
+                    .spawn((
+                        ButtonBundle {
+                            style: Style {
+                                size: Size::new(Val::Px(360.0), Val::Px(20.0)),
+                                position: UiRect {
+                                    left: Val::Percent(10.0),
+                                    ..Default::default()
+                                },
+                                margin: UiRect::all(Val::Px(2.0)),
+                                justify_content: JustifyContent::FlexStart,
+                                align_items: AlignItems::Center,
+                                ..Default::default()
+                            },
+                            background_color: BUTTON_NORMAL_COLOR.into(),
+                            ..Default::default()
+                        },
+                        ButtonAction::PlayReplay(index),
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn(TextBundle {
+                            text: Text::from_section(
+                                format!("#{:<4} {:<8} {}", saved.number, mode, saved.seed),
+                                TextStyle {
+                                    font: fonts.get("fira_mono"),
+                                    font_size: 15.0,
+                                    color: Color::WHITE,
+                                },
+                            ),
+                            ..Default::default()
+                        });
+                    });
+            }
+
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: button_style.button.clone(),
+                        background_color: BUTTON_NORMAL_COLOR.into(),
+                        ..Default::default()
+                    },
+                    ButtonAction::Back,
+                ))
+                .with_children(|parent| {
+                    parent.spawn(ImageBundle {
+                        style: button_style.icon.clone(),
+                        image: UiImage(images.get("exit_icon")),
+                        ..Default::default()
+                    });
+                    parent.spawn(TextBundle {
+                        text: Text::from_section("Back", button_style.text.clone()),
+                        ..Default::default()
+                    });
+                });
+        });
+}
@@ -0,0 +1,335 @@
+use crate::{
+    constants::*,
+    game::{Difficulty, Score, SelfPlay},
+    input::ControlInput,
+    replay::Replays,
+    utils::{storage, GameClock},
+    AppState, GameRng,
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Name of the high score file in the [`storage`].
+const RECORDS_NAME: &str = "records.ron";
+
+/// Keeps the best won runs of every difficulty, and lets the player sign a new one on the score screen.
+pub struct RecordsPlugin;
+
+impl Plugin for RecordsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Records::load())
+            .add_system_set(
+                SystemSet::on_enter(AppState::Score)
+                    .label(RecordSystems)
+                    .with_system(add_record),
+            )
+            .add_system_set(SystemSet::on_update(AppState::Score).with_system(edit_initials))
+            .add_system_set(SystemSet::on_exit(AppState::Score).with_system(save_records));
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, SystemLabel)]
+pub struct RecordSystems;
+
+/// A won run in the high score table.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Record {
+    pub initials: String,
+    /// See [`Score::points`].
+    pub points: i32,
+    pub time: f32,
+    pub hits: i32,
+    pub miss: i32,
+    pub ball_count: i32,
+    pub seed: u64,
+}
+
+/// The run that just entered the table, being signed on the score screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NewRecord {
+    pub difficulty: Difficulty,
+    pub rank: usize,
+    /// Letter of the initials being edited.
+    pub cursor: usize,
+}
+
+/// High score tables of each difficulty, best first, saved to [`RECORDS_NAME`].
+#[derive(Debug, Default, Clone, Resource, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Records {
+    tables: BTreeMap<Difficulty, Vec<Record>>,
+    /// Initials of the last signed record, offered for the next one.
+    initials: String,
+    #[serde(skip)]
+    pub latest: Option<NewRecord>,
+}
+
+impl Records {
+    pub fn table(&self, difficulty: Difficulty) -> &[Record] {
+        self.tables.get(&difficulty).map_or(&[], Vec::as_slice)
+    }
+
+    /// The record being signed, if any.
+    pub fn latest(&self) -> Option<&Record> {
+        let latest = self.latest?;
+        self.table(latest.difficulty).get(latest.rank)
+    }
+
+    /// Puts the record into the table of its difficulty, returning its rank if it is among the best [`RECORDS_COUNT`].
+    pub fn insert(&mut self, difficulty: Difficulty, record: Record) -> Option<usize> {
+        let table = self.tables.entry(difficulty).or_default();
+        let rank = table
+            .iter()
+            .position(|other| record.points > other.points)
+            .unwrap_or(table.len());
+        if rank >= RECORDS_COUNT {
+            return None;
+        }
+
+        table.insert(rank, record);
+        table.truncate(RECORDS_COUNT);
+        Some(rank)
+    }
+
+    pub fn load() -> Self {
+        let Some(text) = storage::read(RECORDS_NAME) else {
+            return Self::default();
+        };
+
+        match ron::from_str(&text) {
+            Ok(records) => records,
+            Err(err) => {
+                warn!("Failed to parse high scores: {err}");
+                storage::back_up(RECORDS_NAME, &text);
+                Self::default()
+            }
+        }
+    }
+
+    pub fn save(&self) {
+        match ron::ser::to_string_pretty(self, Default::default()) {
+            Ok(text) => storage::write(RECORDS_NAME, &text),
+            Err(err) => warn!("Failed to serialize high scores: {err}"),
+        }
+    }
+
+    fn latest_mut(&mut self) -> Option<(&mut Record, &mut NewRecord)> {
+        let latest = self.latest.as_mut()?;
+        let record = self
+            .tables
+            .get_mut(&latest.difficulty)?
+            .get_mut(latest.rank)?;
+        Some((record, latest))
+    }
+}
+
+/// Only live runs played by hand are ranked: a replayed run was recorded when it was played,
+/// and one played by a brain is not the player's.
+fn add_record(
+    clock: Res<GameClock>,
+    score: Res<Score>,
+    rng: Res<GameRng>,
+    difficulty: Res<Difficulty>,
+    replays: Res<Replays>,
+    self_play: Res<SelfPlay>,
+    mut records: ResMut<Records>,
+) {
+    records.latest = None;
+    if replays.played_back() || self_play.brain.is_some() {
+        return;
+    }
+
+    let time = score.time(&clock);
+    let initials = match records.initials.len() {
+        RECORD_INITIALS_LENGTH => records.initials.clone(),
+        _ => "A".repeat(RECORD_INITIALS_LENGTH),
+    };
+    let record = Record {
+        initials,
+        points: score.points(time),
+        time,
+        hits: score.hits,
+        miss: score.miss,
+        ball_count: score.ball_count,
+        seed: rng.seed(),
+    };
+
+    records.latest = records.insert(*difficulty, record).map(|rank| NewRecord {
+        difficulty: *difficulty,
+        rank,
+        cursor: 0,
+    });
+    if records.latest.is_some() {
+        records.save();
+    }
+}
+
+/// Types the initials of the new record with the keyboard,
+/// or picks each letter with the up and down directions and moves between them with left and right.
+fn edit_initials(
+    input: Res<ControlInput>,
+    keys: Res<Input<KeyCode>>,
+    mut characters: EventReader<ReceivedCharacter>,
+    mut records: ResMut<Records>,
+) {
+    let typed: Vec<char> = characters
+        .iter()
+        .map(|event| event.char.to_ascii_uppercase())
+        .filter(char::is_ascii_alphanumeric)
+        .collect();
+    let backspace = keys.just_pressed(KeyCode::Back);
+    // letters may be bound to directions as well, typing takes precedence
+    let navigate = if typed.is_empty() {
+        input.navigate
+    } else {
+        IVec2::ZERO
+    };
+    if typed.is_empty() && !backspace && navigate == IVec2::ZERO {
+        return;
+    }
+
+    let Some((record, latest)) = records.latest_mut() else {
+        return;
+    };
+
+    let mut letters: Vec<char> = record.initials.chars().collect();
+    letters.resize(RECORD_INITIALS_LENGTH, 'A');
+    let last = RECORD_INITIALS_LENGTH - 1;
+
+    for letter in typed {
+        letters[latest.cursor] = letter;
+        latest.cursor = (latest.cursor + 1).min(last);
+    }
+    if backspace {
+        latest.cursor = latest.cursor.saturating_sub(1);
+    }
+
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+    if navigate.y != 0 {
+        let index = ALPHABET
+            .iter()
+            .position(|letter| *letter as char == letters[latest.cursor])
+            .unwrap_or_default() as i32;
+        let index = (index + navigate.y).rem_euclid(ALPHABET.len() as i32);
+        letters[latest.cursor] = ALPHABET[index as usize] as char;
+    }
+    latest.cursor = (latest.cursor as i32 + navigate.x).clamp(0, last as i32) as usize;
+
+    record.initials = letters.into_iter().collect();
+}
+
+fn save_records(mut records: ResMut<Records>) {
+    if let Some(initials) = records.latest().map(|record| record.initials.clone()) {
+        records.initials = initials;
+        records.save();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(initials: &str, points: i32) -> Record {
+        Record {
+            initials: initials.into(),
+            points,
+            time: 0.0,
+            hits: 0,
+            miss: 0,
+            ball_count: 0,
+            seed: 0,
+        }
+    }
+
+    fn initials(records: &Records, difficulty: Difficulty) -> Vec<&str> {
+        records
+            .table(difficulty)
+            .iter()
+            .map(|record| record.initials.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn insert_ranks_by_points() {
+        let mut records = Records::default();
+        assert_eq!(
+            records.insert(Difficulty::Normal, record("BBB", 200)),
+            Some(0)
+        );
+        assert_eq!(
+            records.insert(Difficulty::Normal, record("AAA", 300)),
+            Some(0)
+        );
+        assert_eq!(
+            records.insert(Difficulty::Normal, record("CCC", 100)),
+            Some(2)
+        );
+        assert_eq!(
+            initials(&records, Difficulty::Normal),
+            ["AAA", "BBB", "CCC"]
+        );
+        assert!(records.table(Difficulty::Hard).is_empty());
+    }
+
+    #[test]
+    fn ties_keep_the_older_run_ahead() {
+        let mut records = Records::default();
+        records.insert(Difficulty::Normal, record("OLD", 100));
+        assert_eq!(
+            records.insert(Difficulty::Normal, record("NEW", 100)),
+            Some(1)
+        );
+        assert_eq!(initials(&records, Difficulty::Normal), ["OLD", "NEW"]);
+    }
+
+    #[test]
+    fn full_table_rejects_worse_runs() {
+        let mut records = Records::default();
+        for rank in 0..RECORDS_COUNT {
+            let points = 1000 - rank as i32;
+            assert_eq!(
+                records.insert(Difficulty::Normal, record("AAA", points)),
+                Some(rank)
+            );
+        }
+
+        let last = records.table(Difficulty::Normal)[RECORDS_COUNT - 1].points;
+        assert_eq!(
+            records.insert(Difficulty::Normal, record("TIE", last)),
+            None
+        );
+        assert_eq!(records.insert(Difficulty::Normal, record("LOW", 0)), None);
+        assert_eq!(records.table(Difficulty::Normal).len(), RECORDS_COUNT);
+
+        // a better run pushes the last one out
+        assert_eq!(
+            records.insert(Difficulty::Normal, record("TOP", 2000)),
+            Some(0)
+        );
+        assert_eq!(records.table(Difficulty::Normal).len(), RECORDS_COUNT);
+        assert_eq!(
+            records.table(Difficulty::Normal)[RECORDS_COUNT - 1].points,
+            last + 1
+        );
+    }
+
+    #[test]
+    fn insert_truncates_to_records_count() {
+        // a table saved with more runs than are kept now
+        let mut records = Records::default();
+        records.tables.insert(
+            Difficulty::Normal,
+            (0..RECORDS_COUNT + 5)
+                .map(|rank| record("AAA", 1000 - rank as i32))
+                .collect(),
+        );
+
+        assert_eq!(
+            records.insert(Difficulty::Normal, record("NEW", 2000)),
+            Some(0)
+        );
+        assert_eq!(records.table(Difficulty::Normal).len(), RECORDS_COUNT);
+        assert_eq!(records.table(Difficulty::Normal)[0].initials, "NEW");
+    }
+}
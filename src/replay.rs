@@ -0,0 +1,573 @@
+use crate::{
+    game::{read_player_input, Assist, Difficulty, MultiBall, PlayerInput},
+    utils::{is_paused, storage, GameClock},
+    AppState, GameRng,
+};
+use bevy::{ecs::system::SystemParam, prelude::*, time::TimeUpdateStrategy};
+use std::{fmt, marker::PhantomData, time::Duration};
+
+/// Data directory the runs are saved to, one file per run.
+const REPLAY_DIRECTORY: &str = "replays";
+/// Number of saved runs kept; the oldest one is removed when a new run is saved.
+const REPLAY_LIMIT: usize = 10;
+
+const REPLAY_MAGIC: &[u8; 4] = b"BURP";
+const REPLAY_VERSION: u16 = 2;
+const HEADER_LEN: usize = 4 + 2 + 1 + 1 + 1 + 8 + 4;
+const FRAME_LEN: usize = 4 + 4 + 4;
+const STATE_BATTLE: u8 = 0;
+const STATE_PRACTICE: u8 = 1;
+const FLAG_ASSIST: u8 = 1 << 0;
+const FLAG_MULTI_BALL: u8 = 1 << 1;
+
+/// Records the input of every run in [`AppState::Battle`] and [`AppState::Practice`],
+/// and plays recorded runs back in place of live input.
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Replays>()
+            .add_system_to_stage(CoreStage::PreUpdate, record_input.after(read_player_input))
+            .add_system_to_stage(CoreStage::PreUpdate, play_input.after(read_player_input))
+            .add_system_to_stage(CoreStage::Last, drive_time)
+            .add_system_set(SystemSet::on_enter(AppState::Battle).with_system(enter_run))
+            .add_system_set(SystemSet::on_enter(AppState::Practice).with_system(enter_run))
+            .add_system_set(SystemSet::on_exit(AppState::Battle).with_system(exit_run))
+            .add_system_set(SystemSet::on_exit(AppState::Practice).with_system(exit_run));
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ReplayFrame {
+    /// Duration of the frame, as reported by [`Time::delta`].
+    pub delta_time: Duration,
+    /// Value of [`PlayerInput::delta`] during the frame.
+    pub input: Vec2,
+}
+
+/// Settings that change how a run plays, recorded with it and applied while it is played back.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RunSettings {
+    pub difficulty: Difficulty,
+    pub assist: bool,
+    pub multi_ball: bool,
+}
+
+/// The resources behind [`RunSettings`].
+#[derive(SystemParam)]
+pub struct RunSettingsParam<'w, 's> {
+    difficulty: ResMut<'w, Difficulty>,
+    assist: ResMut<'w, Assist>,
+    multi_ball: ResMut<'w, MultiBall>,
+    #[system_param(ignore)]
+    marker: PhantomData<&'s ()>,
+}
+
+impl<'w, 's> RunSettingsParam<'w, 's> {
+    pub fn get(&self) -> RunSettings {
+        RunSettings {
+            difficulty: *self.difficulty,
+            assist: self.assist.enabled,
+            multi_ball: self.multi_ball.enabled,
+        }
+    }
+
+    pub fn set(&mut self, settings: RunSettings) {
+        *self.difficulty = settings.difficulty;
+        self.assist.enabled = settings.assist;
+        self.multi_ball.enabled = settings.multi_ball;
+    }
+}
+
+/// Input and timing of a single run.
+#[derive(Debug, Clone)]
+pub struct Replay {
+    pub state: AppState,
+    pub settings: RunSettings,
+    pub seed: u64,
+    pub frames: Vec<ReplayFrame>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReplayError {
+    Magic,
+    Version(u16),
+    State(u8),
+    Difficulty(u8),
+    Truncated,
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayError::Magic => write!(f, "not a replay file"),
+            ReplayError::Version(version) => write!(f, "unsupported replay version {version}"),
+            ReplayError::State(state) => write!(f, "unknown game mode {state}"),
+            ReplayError::Difficulty(difficulty) => write!(f, "unknown difficulty {difficulty}"),
+            ReplayError::Truncated => write!(f, "replay file is truncated"),
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+impl Replay {
+    /// Encodes the replay as a header followed by one fixed-size record per frame, all little-endian.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let state = match self.state {
+            AppState::Practice => STATE_PRACTICE,
+            _ => STATE_BATTLE,
+        };
+        let difficulty = Difficulty::ALL
+            .iter()
+            .position(|difficulty| *difficulty == self.settings.difficulty)
+            .unwrap_or_default() as u8;
+        let mut flags = 0;
+        if self.settings.assist {
+            flags |= FLAG_ASSIST;
+        }
+        if self.settings.multi_ball {
+            flags |= FLAG_MULTI_BALL;
+        }
+
+        let mut bytes = Vec::with_capacity(HEADER_LEN + FRAME_LEN * self.frames.len());
+        bytes.extend_from_slice(REPLAY_MAGIC);
+        bytes.extend_from_slice(&REPLAY_VERSION.to_le_bytes());
+        bytes.push(state);
+        bytes.push(difficulty);
+        bytes.push(flags);
+        bytes.extend_from_slice(&self.seed.to_le_bytes());
+        bytes.extend_from_slice(&(self.frames.len() as u32).to_le_bytes());
+        for frame in &self.frames {
+            let nanos = frame.delta_time.as_nanos().min(u32::MAX as u128) as u32;
+            bytes.extend_from_slice(&nanos.to_le_bytes());
+            bytes.extend_from_slice(&frame.input.x.to_le_bytes());
+            bytes.extend_from_slice(&frame.input.y.to_le_bytes());
+        }
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ReplayError> {
+        let read = |offset: usize, len: usize| -> Result<&[u8], ReplayError> {
+            bytes
+                .get(offset..offset + len)
+                .ok_or(ReplayError::Truncated)
+        };
+        let read_u32 = |offset: usize| -> Result<u32, ReplayError> {
+            Ok(u32::from_le_bytes(read(offset, 4)?.try_into().unwrap()))
+        };
+
+        if read(0, 4)? != REPLAY_MAGIC {
+            return Err(ReplayError::Magic);
+        }
+        let version = u16::from_le_bytes(read(4, 2)?.try_into().unwrap());
+        if version != REPLAY_VERSION {
+            return Err(ReplayError::Version(version));
+        }
+        let state = match read(6, 1)?[0] {
+            STATE_BATTLE => AppState::Battle,
+            STATE_PRACTICE => AppState::Practice,
+            state => return Err(ReplayError::State(state)),
+        };
+        let difficulty = read(7, 1)?[0];
+        let difficulty = *Difficulty::ALL
+            .get(difficulty as usize)
+            .ok_or(ReplayError::Difficulty(difficulty))?;
+        let flags = read(8, 1)?[0];
+        let settings = RunSettings {
+            difficulty,
+            assist: flags & FLAG_ASSIST != 0,
+            multi_ball: flags & FLAG_MULTI_BALL != 0,
+        };
+        let seed = u64::from_le_bytes(read(9, 8)?.try_into().unwrap());
+        let len = read_u32(17)? as usize;
+
+        let frames = (0..len)
+            .map(|index| {
+                let offset = HEADER_LEN + index * FRAME_LEN;
+                Ok(ReplayFrame {
+                    delta_time: Duration::from_nanos(read_u32(offset)? as u64),
+                    input: Vec2::new(
+                        f32::from_bits(read_u32(offset + 4)?),
+                        f32::from_bits(read_u32(offset + 8)?),
+                    ),
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            state,
+            settings,
+            seed,
+            frames,
+        })
+    }
+}
+
+/// A run saved in the data directory, described by its file name `<number>-<mode>-<seed>.bur`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SavedReplay {
+    /// Runs are numbered in the order they were saved.
+    pub number: u32,
+    pub state: AppState,
+    pub seed: u64,
+}
+
+impl SavedReplay {
+    fn parse(file_name: &str) -> Option<Self> {
+        let mut parts = file_name.strip_suffix(".bur")?.splitn(3, '-');
+        let number = parts.next()?.parse().ok()?;
+        let state = match parts.next()? {
+            "battle" => AppState::Battle,
+            "practice" => AppState::Practice,
+            _ => return None,
+        };
+        let seed = parts.next()?.parse().ok()?;
+        Some(Self {
+            number,
+            state,
+            seed,
+        })
+    }
+
+    fn path(&self) -> String {
+        let mode = match self.state {
+            AppState::Practice => "practice",
+            _ => "battle",
+        };
+        format!(
+            "{REPLAY_DIRECTORY}/{:05}-{mode}-{}.bur",
+            self.number, self.seed
+        )
+    }
+}
+
+struct Playback {
+    replay: Replay,
+    cursor: usize,
+    started: bool,
+}
+
+#[derive(Default, Resource)]
+pub struct Replays {
+    saved: Vec<SavedReplay>,
+    recording: Option<Replay>,
+    playback: Option<Playback>,
+    /// Whether the last run was a playback rather than a live one.
+    played_back: bool,
+    /// The player's own settings, put back once the playback is over.
+    restore: Option<RunSettings>,
+}
+
+impl Replays {
+    /// Whether the run that just ended was a playback, which does not count as a new run.
+    pub fn played_back(&self) -> bool {
+        self.played_back
+    }
+
+    /// Whether a run is being played back, with the settings it was recorded with.
+    pub fn is_playing(&self) -> bool {
+        self.restore.is_some()
+    }
+
+    /// Saved runs, newest first, as of the last [`Replays::refresh`].
+    pub fn saved(&self) -> &[SavedReplay] {
+        &self.saved
+    }
+
+    /// Lists the runs saved in the data directory.
+    pub fn refresh(&mut self) {
+        self.saved = storage::list_data(REPLAY_DIRECTORY)
+            .iter()
+            .filter_map(|file_name| SavedReplay::parse(file_name))
+            .collect();
+        self.saved
+            .sort_by_key(|saved| std::cmp::Reverse(saved.number));
+    }
+
+    /// Prepares to play the saved run at `index` in [`Replays::saved`], with the settings it was recorded with.
+    /// Returns the state to enter for the playback, if the run could be loaded.
+    pub fn play(
+        &mut self,
+        index: usize,
+        rng: &mut GameRng,
+        settings: &mut RunSettingsParam,
+    ) -> Option<AppState> {
+        let path = self.saved.get(index)?.path();
+        let replay = match Replay::from_bytes(&storage::read_data(&path)?) {
+            Ok(replay) => replay,
+            Err(err) => {
+                warn!("Failed to load replay {path}: {err}");
+                return None;
+            }
+        };
+
+        let state = replay.state.clone();
+        rng.queue_run(replay.seed);
+        self.restore.get_or_insert(settings.get());
+        settings.set(replay.settings);
+        self.playback = Some(Playback {
+            replay,
+            cursor: 0,
+            started: false,
+        });
+        Some(state)
+    }
+
+    /// Saves the run as the newest one, and removes the oldest runs beyond [`REPLAY_LIMIT`].
+    fn save(&mut self, replay: &Replay) {
+        self.refresh();
+        let saved = SavedReplay {
+            number: self.saved.first().map_or(1, |newest| newest.number + 1),
+            state: replay.state.clone(),
+            seed: replay.seed,
+        };
+        let path = saved.path();
+        if let Err(err) = storage::write_data(&path, &replay.to_bytes()) {
+            // the older runs are kept, as nothing replaced them
+            error!("Failed to save the replay {path}: {err}");
+            return;
+        }
+        self.saved.insert(0, saved);
+
+        let limit = self.saved.len().min(REPLAY_LIMIT);
+        for old in self.saved.drain(limit..) {
+            storage::remove_data(&old.path());
+        }
+    }
+}
+
+fn enter_run(
+    app_state: Res<State<AppState>>,
+    mut replays: ResMut<Replays>,
+    settings: RunSettingsParam,
+) {
+    if let Some(playback) = &mut replays.playback {
+        playback.started = true;
+    } else {
+        replays.recording = Some(Replay {
+            state: app_state.current().clone(),
+            settings: settings.get(),
+            seed: 0,
+            frames: vec![],
+        });
+    }
+}
+
+fn exit_run(
+    mut replays: ResMut<Replays>,
+    mut time_update_strategy: ResMut<TimeUpdateStrategy>,
+    rng: Res<GameRng>,
+    mut settings: RunSettingsParam,
+) {
+    replays.played_back = replays.playback.take().is_some();
+    if replays.played_back {
+        *time_update_strategy = TimeUpdateStrategy::Automatic;
+    }
+    if let Some(restore) = replays.restore.take() {
+        settings.set(restore);
+    }
+
+    if let Some(mut replay) = replays.recording.take() {
+        replay.seed = rng.seed();
+        replays.save(&replay);
+    }
+}
+
+/// Frames spent in the pause menu are left out, as the run does not advance during them.
+fn record_input(
+    time: Res<Time>,
+    clock: Res<GameClock>,
+    input: Res<PlayerInput>,
+    mut replays: ResMut<Replays>,
+) {
+    if clock.is_paused() {
+        return;
+    }
+
+    if let Some(replay) = &mut replays.recording {
+        replay.frames.push(ReplayFrame {
+            delta_time: time.delta(),
+            input: input.delta,
+        });
+    }
+}
+
+/// Replaces live input with the recorded one, and leaves the run when the replay is over.
+fn play_input(
+    clock: Res<GameClock>,
+    mut replays: ResMut<Replays>,
+    mut input: ResMut<PlayerInput>,
+    mut time_update_strategy: ResMut<TimeUpdateStrategy>,
+    mut app_state: ResMut<State<AppState>>,
+) {
+    if clock.is_paused() {
+        return;
+    }
+
+    let Some(playback) = replays
+        .playback
+        .as_mut()
+        .filter(|playback| playback.started)
+    else {
+        return;
+    };
+
+    if let Some(frame) = playback.replay.frames.get(playback.cursor) {
+        input.delta = frame.input;
+        playback.cursor += 1;
+    } else {
+        replays.playback = None;
+        *time_update_strategy = TimeUpdateStrategy::Automatic;
+        let _ = app_state.set(AppState::Menu);
+    }
+}
+
+/// Makes the next frame last exactly as long as the recorded one.
+/// The time stands still while the playback is paused.
+fn drive_time(
+    time: Res<Time>,
+    app_state: Res<State<AppState>>,
+    replays: Res<Replays>,
+    mut time_update_strategy: ResMut<TimeUpdateStrategy>,
+) {
+    // unlike the game clock, the state already tells whether the next frame is paused
+    if is_paused(&app_state) {
+        return;
+    }
+
+    let Some(playback) = replays
+        .playback
+        .as_ref()
+        .filter(|playback| playback.started)
+    else {
+        return;
+    };
+
+    if let Some(frame) = playback.replay.frames.get(playback.cursor) {
+        let instant = time.last_update().unwrap_or_else(|| time.startup());
+        *time_update_strategy = TimeUpdateStrategy::ManualInstant(instant + frame.delta_time);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn replay() -> Replay {
+        Replay {
+            state: AppState::Practice,
+            settings: RunSettings {
+                difficulty: Difficulty::Hard,
+                assist: false,
+                multi_ball: true,
+            },
+            seed: 1234,
+            frames: vec![
+                ReplayFrame {
+                    delta_time: Duration::from_micros(16_667),
+                    input: Vec2::new(1.5, -2.0),
+                },
+                ReplayFrame {
+                    delta_time: Duration::from_micros(16_666),
+                    input: Vec2::ZERO,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn bytes_round_trip() {
+        let replay = replay();
+        let decoded = Replay::from_bytes(&replay.to_bytes()).unwrap();
+
+        assert_eq!(decoded.state, replay.state);
+        assert_eq!(decoded.settings, replay.settings);
+        assert_eq!(decoded.seed, replay.seed);
+        assert_eq!(decoded.frames.len(), replay.frames.len());
+        for (decoded, frame) in decoded.frames.iter().zip(&replay.frames) {
+            assert_eq!(decoded.delta_time, frame.delta_time);
+            assert_eq!(decoded.input, frame.input);
+        }
+    }
+
+    #[test]
+    fn truncated_bytes() {
+        let bytes = replay().to_bytes();
+        for len in [0, 3, HEADER_LEN - 1, bytes.len() - 1] {
+            assert_eq!(
+                Replay::from_bytes(&bytes[..len]).unwrap_err(),
+                ReplayError::Truncated,
+                "{len}"
+            );
+        }
+    }
+
+    #[test]
+    fn bad_magic() {
+        let mut bytes = replay().to_bytes();
+        bytes[0] = b'X';
+        assert_eq!(Replay::from_bytes(&bytes).unwrap_err(), ReplayError::Magic);
+    }
+
+    #[test]
+    fn wrong_version() {
+        let mut bytes = replay().to_bytes();
+        bytes[4..6].copy_from_slice(&1u16.to_le_bytes());
+        assert_eq!(
+            Replay::from_bytes(&bytes).unwrap_err(),
+            ReplayError::Version(1)
+        );
+    }
+
+    #[test]
+    fn unknown_mode() {
+        let mut bytes = replay().to_bytes();
+        bytes[6] = 7;
+        assert_eq!(
+            Replay::from_bytes(&bytes).unwrap_err(),
+            ReplayError::State(7)
+        );
+    }
+
+    #[test]
+    fn unknown_difficulty() {
+        let mut bytes = replay().to_bytes();
+        bytes[7] = 9;
+        assert_eq!(
+            Replay::from_bytes(&bytes).unwrap_err(),
+            ReplayError::Difficulty(9)
+        );
+    }
+
+    #[test]
+    fn parse_file_names() {
+        let saved = SavedReplay {
+            number: 12,
+            state: AppState::Battle,
+            seed: 1234,
+        };
+        assert_eq!(
+            SavedReplay::parse("00012-battle-1234.bur"),
+            Some(saved.clone())
+        );
+        assert_eq!(saved.path(), "replays/00012-battle-1234.bur");
+        assert_eq!(
+            SavedReplay::parse("00003-practice-0.bur").map(|saved| saved.state),
+            Some(AppState::Practice)
+        );
+
+        for name in [
+            "00012-battle-1234",
+            "00012-battle-1234.txt",
+            "00012-battle.bur",
+            "00012-versus-1234.bur",
+            "twelve-battle-1234.bur",
+            "00012-battle-seed.bur",
+            "00012-battle-1234-5.bur",
+            ".bur",
+        ] {
+            assert_eq!(SavedReplay::parse(name), None, "{name}");
+        }
+    }
+}
@@ -0,0 +1,283 @@
+use crate::{
+    game::{Assist, Difficulty, MultiBall},
+    input::InputMap,
+    replay::Replays,
+    utils::storage,
+    AudioVolume,
+};
+use bevy::{prelude::*, window::WindowMode};
+use serde::{Deserialize, Serialize};
+
+/// Version of the settings format; bump it and extend [`Settings::migrate`] when the meaning of a field changes.
+pub const SETTINGS_VERSION: u32 = 1;
+
+/// Name of the settings file in the [`storage`].
+const SETTINGS_NAME: &str = "settings.ron";
+
+/// Bindings saved by versions before the settings file, in the working directory.
+#[cfg(not(target_arch = "wasm32"))]
+const LEGACY_INPUT_MAP_PATH: &str = "controls.ron";
+
+/// Loads the saved [`Settings`] into their resources while the plugin is built, before any asset is loaded,
+/// and saves them whenever one of those resources changes.
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        let settings = Settings::load();
+        if settings.is_read_only() {
+            app.insert_resource(ReadOnly);
+        }
+        app.insert_resource(settings.audio_volume)
+            .insert_resource(settings.difficulty)
+            .insert_resource(settings.input_map)
+            .insert_resource(settings.assist)
+            .insert_resource(MultiBall {
+                enabled: settings.multi_ball,
+                ..Default::default()
+            })
+            .insert_resource(settings.display)
+            .add_system_to_stage(CoreStage::Last, save_settings);
+    }
+}
+
+/// Present when the settings file is from a newer version: it is never written,
+/// since this version would drop the fields it does not know.
+#[derive(Resource)]
+struct ReadOnly;
+
+/// Window options, applied when the window is created and whenever they change.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Resource, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DisplaySettings {
+    pub fullscreen: bool,
+}
+
+impl DisplaySettings {
+    pub fn window_mode(&self) -> WindowMode {
+        if self.fullscreen {
+            WindowMode::BorderlessFullscreen
+        } else {
+            WindowMode::Windowed
+        }
+    }
+}
+
+/// Everything the player can change, as saved across launches.
+/// Missing fields take their defaults and unknown fields are ignored, so that older and newer files still load.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// Files without a version are version 0.
+    #[serde(default)]
+    pub version: u32,
+    pub audio_volume: AudioVolume,
+    pub difficulty: Difficulty,
+    pub input_map: InputMap,
+    pub assist: Assist,
+    /// Extra balls join the battle as the hits add up.
+    pub multi_ball: bool,
+    pub display: DisplaySettings,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            version: SETTINGS_VERSION,
+            audio_volume: Default::default(),
+            difficulty: Default::default(),
+            input_map: Default::default(),
+            assist: Default::default(),
+            multi_ball: false,
+            display: Default::default(),
+        }
+    }
+}
+
+impl Settings {
+    /// Loads the saved settings, migrating older ones, or the defaults if there are none.
+    /// Settings from a newer version are loaded as they are, and left for [`SettingsPlugin`] to keep read-only.
+    pub fn load() -> Self {
+        let Some(text) = storage::read(SETTINGS_NAME) else {
+            return Self::from_legacy();
+        };
+
+        match ron::from_str::<Self>(&text) {
+            Ok(settings) => {
+                let version = settings.version;
+                if settings.is_read_only() {
+                    warn!("Settings are from a newer version {version}, they will not be saved");
+                }
+                let settings = settings.migrate();
+                if version < settings.version {
+                    info!("Migrated settings from version {version} to {SETTINGS_VERSION}");
+                    settings.save();
+                }
+                settings
+            }
+            Err(err) => {
+                warn!("Failed to parse settings, using the defaults: {err}");
+                storage::back_up(SETTINGS_NAME, &text);
+                Self::default()
+            }
+        }
+    }
+
+    pub fn save(&self) {
+        match ron::ser::to_string_pretty(self, Default::default()) {
+            Ok(text) => storage::write(SETTINGS_NAME, &text),
+            Err(err) => warn!("Failed to serialize settings: {err}"),
+        }
+    }
+
+    /// Whether the settings are from a newer version, whose fields this one would drop when saving them.
+    fn is_read_only(&self) -> bool {
+        self.version > SETTINGS_VERSION
+    }
+
+    /// Brings settings saved by an older version up to [`SETTINGS_VERSION`]; newer ones keep their version.
+    fn migrate(mut self) -> Self {
+        // version 0 has the same fields as version 1, it only lacks the version itself
+        self.version = self.version.max(SETTINGS_VERSION);
+        // actions added since the file was saved get their default bindings
+        self.input_map.bind_missing();
+        self
+    }
+
+    /// Picks up the bindings saved before there was a settings file.
+    fn from_legacy() -> Self {
+        let mut settings = Self::default();
+
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Ok(text) = std::fs::read_to_string(LEGACY_INPUT_MAP_PATH) {
+            match ron::from_str(&text) {
+                Ok(input_map) => {
+                    info!("Migrated bindings from {LEGACY_INPUT_MAP_PATH}");
+                    settings.input_map = input_map;
+                    settings.save();
+                }
+                Err(err) => warn!("Failed to parse {LEGACY_INPUT_MAP_PATH}: {err}"),
+            }
+        }
+
+        settings
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn save_settings(
+    read_only: Option<Res<ReadOnly>>,
+    replays: Res<Replays>,
+    mut saved: Local<Option<Settings>>,
+    audio_volume: Res<AudioVolume>,
+    difficulty: Res<Difficulty>,
+    input_map: Res<InputMap>,
+    assist: Res<Assist>,
+    multi_ball: Res<MultiBall>,
+    display: Res<DisplaySettings>,
+) {
+    // a playback borrows the settings of the run it plays back
+    if read_only.is_some() || replays.is_playing() {
+        return;
+    }
+    if saved.is_some()
+        && !audio_volume.is_changed()
+        && !difficulty.is_changed()
+        && !input_map.is_changed()
+        && !assist.is_changed()
+        && !multi_ball.is_changed()
+        && !display.is_changed()
+    {
+        return;
+    }
+
+    let settings = Settings {
+        version: SETTINGS_VERSION,
+        audio_volume: *audio_volume,
+        difficulty: *difficulty,
+        input_map: input_map.clone(),
+        assist: *assist,
+        multi_ball: multi_ball.enabled,
+        display: *display,
+    };
+
+    // the first frame only remembers what was loaded
+    if saved.is_some() && saved.as_ref() != Some(&settings) {
+        settings.save();
+    }
+    *saved = Some(settings);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::{Action, Binding};
+
+    fn parse(text: &str) -> Settings {
+        ron::from_str::<Settings>(text).unwrap().migrate()
+    }
+
+    #[test]
+    fn version_0_is_migrated() {
+        let settings = parse("(difficulty: Hard, assist: (enabled: false))");
+
+        assert_eq!(settings.version, SETTINGS_VERSION);
+        assert!(!settings.is_read_only());
+        assert_eq!(settings.difficulty, Difficulty::Hard);
+        assert!(!settings.assist.enabled);
+        assert_eq!(settings.input_map, InputMap::default());
+    }
+
+    #[test]
+    fn unknown_fields_are_ignored() {
+        let settings =
+            parse("(version: 1, difficulty: Easy, future_option: 42, future_table: (a: 1))");
+
+        assert_eq!(settings.version, SETTINGS_VERSION);
+        assert_eq!(settings.difficulty, Difficulty::Easy);
+    }
+
+    #[test]
+    fn missing_actions_get_default_bindings() {
+        let settings = parse("(version: 1, input_map: (bindings: {Up: [Key(I)]}))");
+
+        assert_eq!(
+            settings.input_map.bindings(Action::Up),
+            [Binding::Key(KeyCode::I)]
+        );
+        let defaults = InputMap::default();
+        for action in Action::ALL
+            .into_iter()
+            .filter(|action| *action != Action::Up)
+        {
+            assert_eq!(
+                settings.input_map.bindings(action),
+                defaults.bindings(action),
+                "{action:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn newer_version_stays_read_only() {
+        let version = SETTINGS_VERSION + 1;
+        let settings = parse(&format!("(version: {version}, difficulty: Nightmare)"));
+
+        assert_eq!(settings.version, version);
+        assert!(settings.is_read_only());
+        assert_eq!(settings.difficulty, Difficulty::Nightmare);
+    }
+
+    #[test]
+    fn saved_settings_round_trip() {
+        let mut settings = Settings {
+            difficulty: Difficulty::Hard,
+            multi_ball: true,
+            ..Default::default()
+        };
+        settings.display.fullscreen = true;
+
+        let text = ron::ser::to_string_pretty(&settings, Default::default()).unwrap();
+        assert_eq!(parse(&text), settings);
+    }
+}
@@ -0,0 +1,76 @@
+use super::is_paused;
+use crate::{AppState, TimeScale};
+use bevy::prelude::*;
+use std::time::Duration;
+
+/// Time of the game world, read by game-play systems instead of [`Time`].
+/// It stands still while the run is paused, and its scaled part follows the [`TimeScale`] of slow motion.
+#[derive(Default, Resource)]
+pub struct GameClock {
+    paused: bool,
+    delta: Duration,
+    scaled_delta: Duration,
+    elapsed: Duration,
+    scaled_elapsed: Duration,
+}
+
+impl GameClock {
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Game time of the current frame, zero while paused.
+    pub fn delta(&self) -> Duration {
+        self.delta
+    }
+
+    pub fn delta_seconds(&self) -> f32 {
+        self.delta.as_secs_f32()
+    }
+
+    /// [`GameClock::delta`] slowed down by the [`TimeScale`].
+    pub fn scaled_delta(&self) -> Duration {
+        self.scaled_delta
+    }
+
+    pub fn scaled_delta_seconds(&self) -> f32 {
+        self.scaled_delta.as_secs_f32()
+    }
+
+    /// Game time since startup, without the pauses.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    pub fn elapsed_seconds(&self) -> f32 {
+        self.elapsed.as_secs_f32()
+    }
+
+    /// Sum of every [`GameClock::scaled_delta`] since startup.
+    pub fn scaled_elapsed_seconds(&self) -> f32 {
+        self.scaled_elapsed.as_secs_f32()
+    }
+
+    /// Advances the clock by a frame of `delta` real time, unless `paused`.
+    pub fn update(&mut self, delta: Duration, scale: f32, paused: bool) {
+        self.paused = paused;
+        if paused {
+            self.delta = Duration::ZERO;
+            self.scaled_delta = Duration::ZERO;
+        } else {
+            self.delta = delta;
+            self.scaled_delta = delta.mul_f32(scale.max(0.0));
+        }
+        self.elapsed += self.delta;
+        self.scaled_elapsed += self.scaled_delta;
+    }
+}
+
+pub fn update_game_clock(
+    time: Res<Time>,
+    time_scale: Res<TimeScale>,
+    app_state: Res<State<AppState>>,
+    mut clock: ResMut<GameClock>,
+) {
+    clock.update(time.delta(), time_scale.0, is_paused(&app_state));
+}
@@ -23,6 +23,14 @@ pub enum Hit {
 }
 
 impl Hit {
+    /// The same hit seen from the other collider.
+    pub fn flipped(self) -> Self {
+        match self {
+            Hit::Penetration(x) => Hit::Penetration(flip_penetration(x)),
+            Hit::Cast(x) => Hit::Cast(flip_cast(x)),
+        }
+    }
+
     pub fn normal(&self) -> Vec2 {
         match self {
             Hit::Penetration(x) => x.normal,
@@ -38,11 +46,29 @@ impl Hit {
     }
 }
 
+#[derive(Debug, Clone, Copy)]
+pub enum Shape {
+    /// Axis-aligned box of the given size.
+    Box(Vec2),
+    /// Circle of the given radius.
+    Circle(f32),
+}
+
+impl Shape {
+    /// Size of the bounding box.
+    pub fn size(&self) -> Vec2 {
+        match *self {
+            Shape::Box(size) => size,
+            Shape::Circle(radius) => Vec2::splat(2.0 * radius),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Collider {
     pub previous_position: Vec2,
     pub position: Vec2,
-    pub size: Vec2,
+    pub shape: Shape,
 }
 
 impl Collider {
@@ -59,11 +85,13 @@ fn intersection(a_min: Vec2, a_max: Vec2, b_min: Vec2, b_max: Vec2) -> Vec2 {
 
 /// Axis-aligned bounding box collision with "side" detection
 fn penetrate(a: &Collider, b: &Collider) -> Option<Penetration> {
-    let a_min = a.position - a.size / 2.0;
-    let a_max = a.position + a.size / 2.0;
+    let (a_size, b_size) = (a.shape.size(), b.shape.size());
+
+    let a_min = a.position - a_size / 2.0;
+    let a_max = a.position + a_size / 2.0;
 
-    let b_min = b.position - b.size / 2.0;
-    let b_max = b.position + b.size / 2.0;
+    let b_min = b.position - b_size / 2.0;
+    let b_max = b.position + b_size / 2.0;
 
     // check to see if the two rectangles are intersecting
     if a_min.x < b_max.x && a_max.x > b_min.x && a_min.y < b_max.y && a_max.y > b_min.y {
@@ -122,9 +150,11 @@ fn penetrate(a: &Collider, b: &Collider) -> Option<Penetration> {
 }
 
 fn cast(a: &Collider, b: &Collider) -> Option<Cast> {
+    let (a_size, b_size) = (a.shape.size(), b.shape.size());
+
     let origin = a.previous_position;
     let delta = a.delta() - b.delta();
-    let padding = a.size / 2.0;
+    let padding = a_size / 2.0;
 
     let sign = delta.signum();
     let scale = delta.recip();
@@ -133,8 +163,8 @@ fn cast(a: &Collider, b: &Collider) -> Option<Cast> {
         return None;
     }
 
-    let near_time = (b.position - sign * (b.size / 2.0 + padding) - origin) * scale;
-    let far_time = (b.position + sign * (b.size / 2.0 + padding) - origin) * scale;
+    let near_time = (b.position - sign * (b_size / 2.0 + padding) - origin) * scale;
+    let far_time = (b.position + sign * (b_size / 2.0 + padding) - origin) * scale;
 
     if near_time.x > far_time.y || near_time.y > far_time.x {
         return None;
@@ -151,11 +181,11 @@ fn cast(a: &Collider, b: &Collider) -> Option<Cast> {
         return None;
     }
 
-    let a_min = a.position - a.size / 2.0 + near_time * a.delta();
-    let a_max = a.position + a.size / 2.0 + near_time * a.delta();
+    let a_min = a.position - a_size / 2.0 + near_time * a.delta();
+    let a_max = a.position + a_size / 2.0 + near_time * a.delta();
 
-    let b_min = b.position - b.size / 2.0 + near_time * b.delta();
-    let b_max = b.position + b.size / 2.0 + near_time * b.delta();
+    let b_min = b.position - b_size / 2.0 + near_time * b.delta();
+    let b_max = b.position + b_size / 2.0 + near_time * b.delta();
 
     let location = intersection(a_min, a_max, b_min, b_max);
 
@@ -167,9 +197,273 @@ fn cast(a: &Collider, b: &Collider) -> Option<Cast> {
     })
 }
 
+/// Circle collision against either shape, with the normal pointing from `b` towards `a`.
+fn penetrate_circle(a: &Collider, radius: f32, b: &Collider) -> Option<Penetration> {
+    let offset = a.position - b.position;
+
+    let (normal, depth, location) = match b.shape {
+        Shape::Circle(b_radius) => {
+            let distance = offset.length();
+            let depth = radius + b_radius - distance;
+            if depth <= 0.0 {
+                return None;
+            }
+
+            let normal = offset.try_normalize().unwrap_or(Vec2::Y);
+            let location = b.position + normal * (b_radius - depth / 2.0);
+            (normal, depth, location)
+        }
+        Shape::Box(size) => {
+            let half_size = size / 2.0;
+            let closest = offset.clamp(-half_size, half_size);
+
+            if closest != offset {
+                // the center is outside, so the circle touches a side or a corner
+                let delta = offset - closest;
+                let distance = delta.length();
+                let depth = radius - distance;
+                if depth <= 0.0 {
+                    return None;
+                }
+
+                (delta / distance, depth, b.position + closest)
+            } else {
+                // the center is inside, so push it out of the nearest side
+                let gap = half_size - offset.abs();
+                let (normal, gap) = if gap.x < gap.y {
+                    (offset.x.signum() * Vec2::X, gap.x)
+                } else {
+                    (offset.y.signum() * Vec2::Y, gap.y)
+                };
+
+                (normal, gap + radius, a.position + gap * normal)
+            }
+        }
+    };
+
+    Some(Penetration {
+        normal,
+        location,
+        depth,
+    })
+}
+
+/// Times at which a ray enters and leaves a circle centered at the origin.
+fn ray_circle(origin: Vec2, delta: Vec2, radius: f32) -> Option<(f32, f32)> {
+    let a = delta.length_squared();
+    let b = origin.dot(delta);
+    let c = origin.length_squared() - radius * radius;
+
+    let discriminant = b * b - a * c;
+    if discriminant < 0.0 {
+        return None;
+    }
+
+    let root = discriminant.sqrt();
+    Some(((-b - root) / a, (-b + root) / a))
+}
+
+/// Times at which a ray enters and leaves a box centered at the origin, and the normal of the entering side.
+fn ray_box(origin: Vec2, delta: Vec2, half_size: Vec2) -> Option<(f32, f32, Vec2)> {
+    let mut near_time = f32::NEG_INFINITY;
+    let mut far_time = f32::INFINITY;
+    let mut normal = Vec2::ZERO;
+
+    for (origin, delta, half_size, axis) in [
+        (origin.x, delta.x, half_size.x, Vec2::X),
+        (origin.y, delta.y, half_size.y, Vec2::Y),
+    ] {
+        if delta.abs() < f32::EPSILON {
+            if origin.abs() > half_size {
+                return None;
+            }
+            continue;
+        }
+
+        let sign = delta.signum();
+        let near = (-sign * half_size - origin) / delta;
+        let far = (sign * half_size - origin) / delta;
+
+        if near > near_time {
+            near_time = near;
+            normal = -sign * axis;
+        }
+        far_time = far_time.min(far);
+    }
+
+    (near_time <= far_time).then_some((near_time, far_time, normal))
+}
+
+/// Box continuous collision against a box, as a ray against `b` grown by the size of `a`.
+/// Unlike [`cast`], motion along a single axis is handled. The normal points from `b` towards `a`.
+fn cast_box(a: &Collider, b: &Collider) -> Option<Cast> {
+    let (a_size, b_size) = (a.shape.size(), b.shape.size());
+
+    // move in the frame of b
+    let origin = a.previous_position - b.previous_position;
+    let delta = a.delta() - b.delta();
+
+    if delta.length_squared() < f32::EPSILON {
+        return None;
+    }
+
+    let (near_time, far_time, normal) = ray_box(origin, delta, (a_size + b_size) / 2.0)?;
+    if near_time >= 1.0 || far_time <= 0.0 {
+        return None;
+    }
+
+    let a_position = a.previous_position + near_time * a.delta();
+    let b_position = b.previous_position + near_time * b.delta();
+    let location = intersection(
+        a_position - a_size / 2.0,
+        a_position + a_size / 2.0,
+        b_position - b_size / 2.0,
+        b_position + b_size / 2.0,
+    );
+
+    Some(Cast {
+        normal,
+        location,
+        near_time,
+        far_time,
+    })
+}
+
+/// Circle continuous collision against either shape, with the normal pointing from `b` towards `a`.
+fn cast_circle(a: &Collider, radius: f32, b: &Collider) -> Option<Cast> {
+    // move in the frame of b
+    let origin = a.previous_position - b.previous_position;
+    let delta = a.delta() - b.delta();
+
+    if delta.length_squared() < f32::EPSILON {
+        return None;
+    }
+
+    let (near_time, far_time, normal) = match b.shape {
+        Shape::Circle(b_radius) => {
+            let (near_time, far_time) = ray_circle(origin, delta, radius + b_radius)?;
+            let normal = (origin + near_time * delta).normalize_or_zero();
+            (near_time, far_time, normal)
+        }
+        Shape::Box(size) => {
+            // sweep against the box grown by the radius, then round off its corners
+            let half_size = size / 2.0;
+            let (near_time, far_time, normal) = ray_box(origin, delta, half_size + radius)?;
+
+            let point = origin + near_time * delta;
+            if radius > 0.0 && point.x.abs() > half_size.x && point.y.abs() > half_size.y {
+                let corner = half_size * point.signum();
+                let (near_time, _) = ray_circle(origin - corner, delta, radius)?;
+                let normal = (origin + near_time * delta - corner).normalize_or_zero();
+                (near_time, far_time, normal)
+            } else {
+                (near_time, far_time, normal)
+            }
+        }
+    };
+
+    if near_time >= 1.0 || far_time <= 0.0 {
+        return None;
+    }
+
+    let location =
+        b.previous_position + near_time * b.delta() + origin + near_time * delta - radius * normal;
+
+    Some(Cast {
+        normal,
+        location,
+        near_time,
+        far_time,
+    })
+}
+
+fn flip_penetration(x: Penetration) -> Penetration {
+    Penetration {
+        normal: -x.normal,
+        ..x
+    }
+}
+
+fn flip_cast(x: Cast) -> Cast {
+    Cast {
+        normal: -x.normal,
+        ..x
+    }
+}
+
+/// Continuous collision between boxes and circles, ignoring whether they overlap at the end of the motion.
+/// The normal points from `b` towards `a`.
+pub fn sweep(a: &Collider, b: &Collider) -> Option<Cast> {
+    match (a.shape, b.shape) {
+        (Shape::Box(_), Shape::Box(_)) => cast(a, b),
+        (Shape::Circle(radius), _) => cast_circle(a, radius, b),
+        (Shape::Box(_), Shape::Circle(radius)) => cast_circle(b, radius, a).map(flip_cast),
+    }
+}
+
+/// Like [`sweep`], but a box against a box is cast as a ray against the grown box, so that motion along a single axis hits as well.
+/// Meant for queries; the time of impact solver keeps [`sweep`], as it would otherwise stall on boxes resting against each other.
+pub fn cast_shape(a: &Collider, b: &Collider) -> Option<Cast> {
+    match (a.shape, b.shape) {
+        (Shape::Box(_), Shape::Box(_)) => cast_box(a, b),
+        _ => sweep(a, b),
+    }
+}
+
+/// Whether two boxes or circles overlap at their current positions, including when one contains the other.
+/// The normal points from `b` towards `a`, along the shallowest way out.
+pub fn overlap(a: &Collider, b: &Collider) -> Option<Penetration> {
+    match (a.shape, b.shape) {
+        (Shape::Box(a_size), Shape::Box(b_size)) => {
+            let offset = a.position - b.position;
+            let overlap = (a_size + b_size) / 2.0 - offset.abs();
+            if overlap.x <= 0.0 || overlap.y <= 0.0 {
+                return None;
+            }
+
+            let sign = |x: f32| if x < 0.0 { -1.0 } else { 1.0 };
+            let (normal, depth) = if overlap.x < overlap.y {
+                (sign(offset.x) * Vec2::X, overlap.x)
+            } else {
+                (sign(offset.y) * Vec2::Y, overlap.y)
+            };
+            let location = intersection(
+                a.position - a_size / 2.0,
+                a.position + a_size / 2.0,
+                b.position - b_size / 2.0,
+                b.position + b_size / 2.0,
+            );
+
+            Some(Penetration {
+                normal,
+                location,
+                depth,
+            })
+        }
+        (Shape::Circle(radius), _) => penetrate_circle(a, radius, b),
+        (Shape::Box(_), Shape::Circle(radius)) => {
+            penetrate_circle(b, radius, a).map(flip_penetration)
+        }
+    }
+}
+
+/// Continuous collision between boxes and circles.
+/// Returns collision time information, with the normal pointing from `b` towards `a`.
+pub fn collide(a: &Collider, b: &Collider) -> Option<Hit> {
+    match (a.shape, b.shape) {
+        (Shape::Box(_), Shape::Box(_)) => collide_boxes(a, b),
+        (Shape::Circle(radius), _) => penetrate_circle(a, radius, b)
+            .map(Hit::Penetration)
+            .or_else(|| cast_circle(a, radius, b).map(Hit::Cast)),
+        (Shape::Box(_), Shape::Circle(radius)) => penetrate_circle(b, radius, a)
+            .map(|x| Hit::Penetration(flip_penetration(x)))
+            .or_else(|| cast_circle(b, radius, a).map(|x| Hit::Cast(flip_cast(x)))),
+    }
+}
+
 /// Axis-aligned bounding box continuous collision.
 /// Returns collision time information
-pub fn collide(a: &Collider, b: &Collider) -> Option<Hit> {
+fn collide_boxes(a: &Collider, b: &Collider) -> Option<Hit> {
     // check if already overlapped
     if let Some(x) = penetrate(a, b) {
         return Some(Hit::Penetration(x));
@@ -189,3 +483,367 @@ pub fn collide(a: &Collider, b: &Collider) -> Option<Hit> {
 
     None
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::{FRAC_1_SQRT_2, SQRT_2};
+
+    fn collider(previous_position: Vec2, position: Vec2, shape: Shape) -> Collider {
+        Collider {
+            previous_position,
+            position,
+            shape,
+        }
+    }
+
+    fn still(position: Vec2, shape: Shape) -> Collider {
+        collider(position, position, shape)
+    }
+
+    fn assert_near(a: f32, b: f32) {
+        assert!((a - b).abs() < 1e-4, "{a} != {b}");
+    }
+
+    #[test]
+    fn cast_shape_box_straight_down() {
+        let a = collider(
+            Vec2::new(0.0, 20.0),
+            Vec2::ZERO,
+            Shape::Box(Vec2::splat(10.0)),
+        );
+        let b = still(Vec2::ZERO, Shape::Box(Vec2::new(20.0, 10.0)));
+
+        let x = cast_shape(&a, &b).unwrap();
+        assert_near(x.near_time, 0.5);
+        assert_eq!(x.normal, Vec2::Y);
+        assert_near(x.location.y, 5.0);
+    }
+
+    #[test]
+    fn cast_shape_box_sideways() {
+        let a = collider(
+            Vec2::new(-30.0, 0.0),
+            Vec2::new(10.0, 0.0),
+            Shape::Box(Vec2::splat(10.0)),
+        );
+        let b = still(Vec2::ZERO, Shape::Box(Vec2::splat(10.0)));
+
+        let x = cast_shape(&a, &b).unwrap();
+        assert_near(x.near_time, 0.5);
+        assert_eq!(x.normal, -Vec2::X);
+    }
+
+    #[test]
+    fn cast_shape_box_passing_by() {
+        let a = collider(
+            Vec2::new(-30.0, 20.0),
+            Vec2::new(30.0, 20.0),
+            Shape::Box(Vec2::splat(10.0)),
+        );
+        let b = still(Vec2::ZERO, Shape::Box(Vec2::splat(10.0)));
+
+        assert!(cast_shape(&a, &b).is_none());
+    }
+
+    #[test]
+    fn overlap_box_containing_box() {
+        let area = still(Vec2::ZERO, Shape::Box(Vec2::splat(100.0)));
+        let body = still(Vec2::new(10.0, 0.0), Shape::Box(Vec2::new(20.0, 10.0)));
+
+        assert!(overlap(&area, &body).is_some());
+        assert!(overlap(&body, &area).is_some());
+    }
+
+    #[test]
+    fn overlap_box_crossing_box() {
+        // wider than the body but shorter, so that neither contains a corner of the other
+        let area = still(Vec2::ZERO, Shape::Box(Vec2::new(100.0, 4.0)));
+        let body = still(Vec2::ZERO, Shape::Box(Vec2::new(20.0, 10.0)));
+
+        let x = overlap(&area, &body).unwrap();
+        assert_eq!(x.normal, Vec2::Y);
+        assert_near(x.depth, 7.0);
+    }
+
+    #[test]
+    fn overlap_box_apart() {
+        let area = still(Vec2::ZERO, Shape::Box(Vec2::splat(10.0)));
+        let body = still(Vec2::new(10.0, 0.0), Shape::Box(Vec2::splat(10.0)));
+
+        assert!(overlap(&area, &body).is_none());
+    }
+
+    #[test]
+    fn overlap_box_containing_circle() {
+        let area = still(Vec2::ZERO, Shape::Box(Vec2::splat(100.0)));
+        let ball = still(Vec2::new(5.0, 5.0), Shape::Circle(4.0));
+
+        assert!(overlap(&area, &ball).is_some());
+        assert!(overlap(&area, &still(Vec2::new(60.0, 0.0), Shape::Circle(4.0))).is_none());
+    }
+
+    #[test]
+    fn penetrate_circle_circle() {
+        let a = still(Vec2::new(3.0, 0.0), Shape::Circle(2.0));
+        let b = still(Vec2::ZERO, Shape::Circle(2.0));
+
+        let x = penetrate_circle(&a, 2.0, &b).unwrap();
+        assert_eq!(x.normal, Vec2::X);
+        assert_near(x.depth, 1.0);
+        assert_near(x.location.x, 1.5);
+    }
+
+    #[test]
+    fn penetrate_circle_concentric() {
+        let a = still(Vec2::ZERO, Shape::Circle(2.0));
+        let b = still(Vec2::ZERO, Shape::Circle(2.0));
+
+        let x = penetrate_circle(&a, 2.0, &b).unwrap();
+        assert_eq!(x.normal, Vec2::Y);
+        assert_near(x.depth, 4.0);
+    }
+
+    #[test]
+    fn penetrate_circle_box_side() {
+        let a = still(Vec2::new(6.0, 0.0), Shape::Circle(2.0));
+        let b = still(Vec2::ZERO, Shape::Box(Vec2::splat(10.0)));
+
+        let x = penetrate_circle(&a, 2.0, &b).unwrap();
+        assert_eq!(x.normal, Vec2::X);
+        assert_near(x.depth, 1.0);
+        assert_eq!(x.location, Vec2::new(5.0, 0.0));
+    }
+
+    #[test]
+    fn penetrate_circle_box_corner() {
+        let a = still(Vec2::new(6.0, 6.0), Shape::Circle(2.0));
+        let b = still(Vec2::ZERO, Shape::Box(Vec2::splat(10.0)));
+
+        let x = penetrate_circle(&a, 2.0, &b).unwrap();
+        assert_near(x.normal.x, FRAC_1_SQRT_2);
+        assert_near(x.normal.y, FRAC_1_SQRT_2);
+        assert_near(x.depth, 2.0 - SQRT_2);
+        assert_eq!(x.location, Vec2::splat(5.0));
+    }
+
+    #[test]
+    fn penetrate_circle_inside_box() {
+        let a = still(Vec2::new(3.0, 0.0), Shape::Circle(1.0));
+        let b = still(Vec2::ZERO, Shape::Box(Vec2::splat(10.0)));
+
+        let x = penetrate_circle(&a, 1.0, &b).unwrap();
+        assert_eq!(x.normal, Vec2::X);
+        assert_near(x.depth, 3.0);
+    }
+
+    #[test]
+    fn penetrate_circle_apart() {
+        let a = still(Vec2::new(8.0, 0.0), Shape::Circle(2.0));
+        let b = still(Vec2::ZERO, Shape::Box(Vec2::splat(10.0)));
+
+        assert!(penetrate_circle(&a, 2.0, &b).is_none());
+    }
+
+    #[test]
+    fn ray_box_axis_aligned() {
+        let (near_time, far_time, normal) = ray_box(
+            Vec2::new(-10.0, 0.0),
+            Vec2::new(20.0, 0.0),
+            Vec2::splat(5.0),
+        )
+        .unwrap();
+        assert_near(near_time, 0.25);
+        assert_near(far_time, 0.75);
+        assert_eq!(normal, -Vec2::X);
+
+        let (near_time, _, normal) = ray_box(
+            Vec2::new(0.0, -10.0),
+            Vec2::new(0.0, 20.0),
+            Vec2::splat(5.0),
+        )
+        .unwrap();
+        assert_near(near_time, 0.25);
+        assert_eq!(normal, -Vec2::Y);
+    }
+
+    #[test]
+    fn ray_box_zero_motion() {
+        // outside the slab of the axis it does not move along
+        assert!(ray_box(
+            Vec2::new(10.0, -10.0),
+            Vec2::new(0.0, 20.0),
+            Vec2::splat(5.0)
+        )
+        .is_none());
+        // inside, from the start
+        let (near_time, far_time, _) = ray_box(Vec2::ZERO, Vec2::ZERO, Vec2::splat(5.0)).unwrap();
+        assert!(near_time < 0.0 && far_time > 0.0);
+    }
+
+    #[test]
+    fn ray_box_corner() {
+        let (near_time, _, _) =
+            ray_box(Vec2::splat(-10.0), Vec2::splat(20.0), Vec2::splat(5.0)).unwrap();
+        assert_near(near_time, 0.25);
+        assert!(ray_box(
+            Vec2::new(-10.0, 0.0),
+            Vec2::new(20.0, 20.0),
+            Vec2::splat(4.0)
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn cast_circle_box_axis_aligned() {
+        let a = collider(Vec2::new(0.0, 10.0), Vec2::ZERO, Shape::Circle(1.0));
+        let b = still(Vec2::ZERO, Shape::Box(Vec2::splat(10.0)));
+
+        let x = cast_circle(&a, 1.0, &b).unwrap();
+        assert_near(x.near_time, 0.4);
+        assert_eq!(x.normal, Vec2::Y);
+        assert_near(x.location.y, 5.0);
+    }
+
+    #[test]
+    fn cast_circle_box_corner() {
+        let a = collider(Vec2::splat(10.0), Vec2::ZERO, Shape::Circle(1.0));
+        let b = still(Vec2::ZERO, Shape::Box(Vec2::splat(10.0)));
+
+        let x = cast_circle(&a, 1.0, &b).unwrap();
+        assert_near(x.near_time, (5.0 - FRAC_1_SQRT_2) / 10.0);
+        assert_near(x.normal.x, FRAC_1_SQRT_2);
+        assert_near(x.normal.y, FRAC_1_SQRT_2);
+        assert_near(x.location.x, 5.0);
+        assert_near(x.location.y, 5.0);
+    }
+
+    #[test]
+    fn cast_circle_circle() {
+        let a = collider(Vec2::new(10.0, 0.0), Vec2::ZERO, Shape::Circle(1.0));
+        let b = still(Vec2::ZERO, Shape::Circle(1.0));
+
+        let x = cast_circle(&a, 1.0, &b).unwrap();
+        assert_near(x.near_time, 0.8);
+        assert_eq!(x.normal, Vec2::X);
+        assert_near(x.location.x, 1.0);
+    }
+
+    #[test]
+    fn cast_circle_zero_motion() {
+        let a = still(Vec2::new(0.0, 10.0), Shape::Circle(1.0));
+        let b = still(Vec2::ZERO, Shape::Box(Vec2::splat(10.0)));
+
+        assert!(cast_circle(&a, 1.0, &b).is_none());
+    }
+
+    #[test]
+    fn cast_circle_short_of_target() {
+        let a = collider(
+            Vec2::new(0.0, 10.0),
+            Vec2::new(0.0, 7.0),
+            Shape::Circle(1.0),
+        );
+        let b = still(Vec2::ZERO, Shape::Box(Vec2::splat(10.0)));
+
+        assert!(cast_circle(&a, 1.0, &b).is_none());
+    }
+
+    #[test]
+    fn sweep_box_against_circle() {
+        let a = collider(
+            Vec2::new(0.0, 20.0),
+            Vec2::ZERO,
+            Shape::Box(Vec2::splat(10.0)),
+        );
+        let b = still(Vec2::ZERO, Shape::Circle(5.0));
+
+        let x = sweep(&a, &b).unwrap();
+        assert_near(x.near_time, 0.5);
+        assert_eq!(x.normal, Vec2::Y);
+    }
+
+    #[test]
+    fn cast_shape_zero_motion() {
+        let a = still(Vec2::new(0.0, 20.0), Shape::Box(Vec2::splat(10.0)));
+        let b = still(Vec2::ZERO, Shape::Box(Vec2::splat(10.0)));
+
+        assert!(cast_shape(&a, &b).is_none());
+    }
+
+    #[test]
+    fn cast_shape_moving_apart() {
+        let a = collider(
+            Vec2::new(0.0, 20.0),
+            Vec2::new(0.0, 40.0),
+            Shape::Box(Vec2::splat(10.0)),
+        );
+        let b = still(Vec2::ZERO, Shape::Box(Vec2::splat(10.0)));
+
+        assert!(cast_shape(&a, &b).is_none());
+    }
+
+    #[test]
+    fn collide_overlapping_boxes() {
+        let a = still(Vec2::new(8.0, 0.0), Shape::Box(Vec2::splat(10.0)));
+        let b = still(Vec2::ZERO, Shape::Box(Vec2::splat(10.0)));
+
+        let Some(Hit::Penetration(x)) = collide(&a, &b) else {
+            panic!("expected a penetration");
+        };
+        assert_eq!(x.normal, Vec2::X);
+    }
+
+    #[test]
+    fn collide_box_cast() {
+        let a = collider(
+            Vec2::new(-20.0, 20.0),
+            Vec2::ZERO,
+            Shape::Box(Vec2::splat(10.0)),
+        );
+        let b = still(Vec2::ZERO, Shape::Box(Vec2::splat(10.0)));
+
+        let Some(Hit::Cast(x)) = collide(&a, &b) else {
+            panic!("expected a cast");
+        };
+        assert_near(x.near_time, 0.5);
+    }
+
+    #[test]
+    fn collide_circle_contained_in_box() {
+        let a = still(Vec2::ZERO, Shape::Box(Vec2::splat(100.0)));
+        let b = still(Vec2::new(10.0, 0.0), Shape::Circle(2.0));
+
+        let Some(Hit::Penetration(x)) = collide(&a, &b) else {
+            panic!("expected a penetration");
+        };
+        // the normal points from the circle towards the box
+        assert_eq!(x.normal, -Vec2::X);
+    }
+
+    #[test]
+    fn collide_circle_tunnelling() {
+        // tunnels through the box within the frame
+        let a = collider(
+            Vec2::new(0.0, -10.0),
+            Vec2::new(0.0, 10.0),
+            Shape::Circle(1.0),
+        );
+        let b = still(Vec2::ZERO, Shape::Box(Vec2::splat(10.0)));
+
+        let Some(Hit::Cast(x)) = collide(&a, &b) else {
+            panic!("expected a cast");
+        };
+        assert_near(x.near_time, 0.2);
+        assert_eq!(x.normal, -Vec2::Y);
+    }
+
+    #[test]
+    fn collide_apart() {
+        let a = still(Vec2::new(20.0, 0.0), Shape::Circle(1.0));
+        let b = still(Vec2::ZERO, Shape::Box(Vec2::splat(10.0)));
+
+        assert!(collide(&a, &b).is_none());
+        assert!(collide(&b, &a).is_none());
+    }
+}
@@ -1,13 +1,18 @@
+mod clock;
 mod collide;
 mod damp;
 mod interpolation;
+mod step;
+pub mod storage;
 
-use crate::AppState;
-use bevy::prelude::*;
+use crate::{input::ControlInput, AppState};
+use bevy::{ecs::schedule::ShouldRun, prelude::*};
 
+pub use clock::*;
 pub use collide::*;
 pub use damp::*;
 pub use interpolation::*;
+pub use step::*;
 
 pub fn cleanup_system<T: Component>(mut commands: Commands, query: Query<Entity, With<T>>) {
     for entity in query.iter() {
@@ -15,9 +20,42 @@ pub fn cleanup_system<T: Component>(mut commands: Commands, query: Query<Entity,
     }
 }
 
-pub fn escape_system(mut app_state: ResMut<State<AppState>>, mut input: ResMut<Input<KeyCode>>) {
-    if input.just_pressed(KeyCode::Escape) {
-        input.reset(KeyCode::Escape);
-        app_state.set(AppState::Menu).unwrap();
+pub fn escape_system(mut app_state: ResMut<State<AppState>>, mut input: ResMut<ControlInput>) {
+    if input.back {
+        input.back = false;
+        // screens opened from the pause menu go back to it
+        if app_state.inactives().is_empty() {
+            app_state.set(AppState::Menu).unwrap();
+        } else {
+            app_state.pop().unwrap();
+        }
+    }
+}
+
+/// Opens the pause menu over the run in progress, and closes it again.
+pub fn pause_system(mut app_state: ResMut<State<AppState>>, mut input: ResMut<ControlInput>) {
+    if input.pause || input.back {
+        input.pause = false;
+        input.back = false;
+        // the run may be ending in this very frame
+        let _ = if *app_state.current() == AppState::Paused {
+            app_state.pop()
+        } else {
+            app_state.push(AppState::Paused)
+        };
+    }
+}
+
+/// Whether the run in progress is paused, including while a screen opened from the pause menu is shown.
+pub fn is_paused(app_state: &State<AppState>) -> bool {
+    *app_state.current() == AppState::Paused || app_state.inactives().contains(&AppState::Paused)
+}
+
+/// Run criteria that stops game-play systems while the [`GameClock`] is paused.
+pub fn unpaused(clock: Res<GameClock>) -> ShouldRun {
+    if clock.is_paused() {
+        ShouldRun::No
+    } else {
+        ShouldRun::Yes
     }
 }
@@ -0,0 +1,38 @@
+use super::GameClock;
+use bevy::{ecs::schedule::ShouldRun, prelude::*, utils::HashMap};
+use std::time::Duration;
+
+/// Progress of the fixed time steps created by [`fixed_step`].
+#[derive(Default, Resource)]
+pub struct FixedSteps {
+    origin: Duration,
+    counts: HashMap<&'static str, u32>,
+}
+
+impl FixedSteps {
+    /// Restarts every fixed step from the current time, so that a run steps at the same moments every time.
+    pub fn reset(&mut self, clock: &GameClock) {
+        self.origin = clock.elapsed();
+        self.counts.clear();
+    }
+}
+
+/// Run criteria that runs a system set once every `step` seconds of [`GameClock`] time,
+/// so that it holds while the game is paused and does not catch up afterwards.
+/// Unlike [`bevy::time::FixedTimestep`], it can be restarted with [`FixedSteps::reset`].
+pub fn fixed_step(
+    label: &'static str,
+    step: f32,
+) -> impl FnMut(Res<GameClock>, ResMut<FixedSteps>) -> ShouldRun {
+    let step = Duration::from_secs_f32(step);
+    move |clock, mut steps| {
+        let origin = steps.origin;
+        let count = steps.counts.entry(label).or_default();
+        if origin + step * (*count + 1) <= clock.elapsed() {
+            *count += 1;
+            ShouldRun::YesAndCheckAgain
+        } else {
+            ShouldRun::No
+        }
+    }
+}
@@ -0,0 +1,228 @@
+//! Small text files kept across launches: in the platform config directory on native builds,
+//! and in the browser's `localStorage` on the web. Failures are logged, never fatal.
+//!
+//! Binary data, such as replays, goes to the platform data directory instead,
+//! and is stored base64-encoded on the web. Failing to write it is left to the caller to report.
+
+const DIRECTORY: &str = "bounce-up";
+
+#[cfg(not(target_arch = "wasm32"))]
+mod platform {
+    use super::DIRECTORY;
+    use bevy::prelude::*;
+    use std::path::{Path, PathBuf};
+
+    /// The file in the platform config directory, or in the working directory if there is none.
+    fn path(name: &str) -> PathBuf {
+        dirs::config_dir()
+            .map(|dir| dir.join(DIRECTORY))
+            .unwrap_or_default()
+            .join(name)
+    }
+
+    pub fn read(name: &str) -> Option<String> {
+        std::fs::read_to_string(path(name)).ok()
+    }
+
+    pub fn write(name: &str, text: &str) {
+        let path = path(name);
+        if let Err(err) = save(&path, text.as_bytes()) {
+            warn!("Failed to save {}: {err}", path.display());
+        }
+    }
+
+    /// The file in the platform data directory, or in the working directory if there is none.
+    fn data_path(name: &str) -> PathBuf {
+        dirs::data_dir()
+            .map(|dir| dir.join(DIRECTORY))
+            .unwrap_or_default()
+            .join(name)
+    }
+
+    pub fn read_data(name: &str) -> Option<Vec<u8>> {
+        std::fs::read(data_path(name)).ok()
+    }
+
+    pub fn write_data(name: &str, bytes: &[u8]) -> Result<(), String> {
+        save(&data_path(name), bytes).map_err(|err| err.to_string())
+    }
+
+    pub fn remove_data(name: &str) {
+        let path = data_path(name);
+        if let Err(err) = std::fs::remove_file(&path) {
+            warn!("Failed to remove {}: {err}", path.display());
+        }
+    }
+
+    pub fn list_data(dir: &str) -> Vec<String> {
+        let Ok(entries) = std::fs::read_dir(data_path(dir)) else {
+            return vec![];
+        };
+        entries
+            .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+            .collect()
+    }
+
+    fn save(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, bytes)
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+mod platform {
+    use super::{base64_decode, base64_encode, DIRECTORY};
+    use bevy::prelude::*;
+
+    fn key(name: &str) -> String {
+        format!("{DIRECTORY}/{name}")
+    }
+
+    fn local_storage() -> Option<web_sys::Storage> {
+        web_sys::window()?.local_storage().ok()?
+    }
+
+    pub fn read(name: &str) -> Option<String> {
+        local_storage()?.get_item(&key(name)).ok()?
+    }
+
+    pub fn write(name: &str, text: &str) {
+        let result = local_storage().map(|storage| storage.set_item(&key(name), text));
+        if !matches!(result, Some(Ok(()))) {
+            warn!("Failed to save {name} to localStorage");
+        }
+    }
+
+    fn data_key(name: &str) -> String {
+        format!("{DIRECTORY}/data/{name}")
+    }
+
+    pub fn read_data(name: &str) -> Option<Vec<u8>> {
+        let text = local_storage()?.get_item(&data_key(name)).ok()??;
+        base64_decode(&text)
+    }
+
+    pub fn write_data(name: &str, bytes: &[u8]) -> Result<(), String> {
+        let storage = local_storage().ok_or("localStorage is not available")?;
+        storage
+            .set_item(&data_key(name), &base64_encode(bytes))
+            .map_err(|err| format!("localStorage refused it: {err:?}"))
+    }
+
+    pub fn remove_data(name: &str) {
+        if let Some(storage) = local_storage() {
+            let _ = storage.remove_item(&data_key(name));
+        }
+    }
+
+    pub fn list_data(dir: &str) -> Vec<String> {
+        let Some(storage) = local_storage() else {
+            return vec![];
+        };
+        let prefix = data_key(&format!("{dir}/"));
+        (0..storage.length().unwrap_or_default())
+            .filter_map(|index| storage.key(index).ok()?)
+            .filter_map(|key| Some(key.strip_prefix(&prefix)?.to_owned()))
+            .collect()
+    }
+}
+
+pub use platform::{read, write};
+
+// `list_data` gives the names of the files in a data directory, in no particular order
+pub use platform::{list_data, read_data, remove_data, write_data};
+
+#[cfg(any(target_arch = "wasm32", test))]
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Standard base64 with padding, which takes a third more room than the bytes instead of twice as much as hex.
+#[cfg(any(target_arch = "wasm32", test))]
+fn base64_encode(bytes: &[u8]) -> String {
+    let mut text = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let word = chunk.iter().enumerate().fold(0u32, |word, (index, &byte)| {
+            word | (byte as u32) << (16 - 8 * index)
+        });
+        for index in 0..4 {
+            if index <= chunk.len() {
+                text.push(BASE64_ALPHABET[(word >> (18 - 6 * index) & 0x3f) as usize] as char);
+            } else {
+                text.push('=');
+            }
+        }
+    }
+    text
+}
+
+/// Reverses [`base64_encode`], or gives [`None`] if the text is not valid base64.
+#[cfg(any(target_arch = "wasm32", test))]
+fn base64_decode(text: &str) -> Option<Vec<u8>> {
+    if !text.len().is_multiple_of(4) {
+        return None;
+    }
+
+    let chunks = text.len() / 4;
+    let mut bytes = Vec::with_capacity(chunks * 3);
+    for (index, chunk) in text.as_bytes().chunks(4).enumerate() {
+        // only the last chunk may be padded
+        let padding = chunk.iter().rev().take_while(|&&c| c == b'=').count();
+        if padding > 2 || (padding > 0 && index + 1 < chunks) {
+            return None;
+        }
+
+        let mut word = 0u32;
+        for (index, &c) in chunk[..4 - padding].iter().enumerate() {
+            let value = BASE64_ALPHABET.iter().position(|&a| a == c)? as u32;
+            word |= value << (18 - 6 * index);
+        }
+        bytes.extend((0..3 - padding).map(|index| (word >> (16 - 8 * index)) as u8));
+    }
+    Some(bytes)
+}
+
+/// Keeps a file that failed to load next to it as `<name>.bak`, so that it is not lost when saved again.
+pub fn back_up(name: &str, text: &str) {
+    write(&format!("{name}.bak"), text);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base64_known_values() {
+        for (bytes, text) in [
+            (&b""[..], ""),
+            (b"f", "Zg=="),
+            (b"fo", "Zm8="),
+            (b"foo", "Zm9v"),
+            (b"foob", "Zm9vYg=="),
+            (b"fooba", "Zm9vYmE="),
+            (b"foobar", "Zm9vYmFy"),
+            (&[0xfb, 0xff, 0x00], "+/8A"),
+        ] {
+            assert_eq!(base64_encode(bytes), text);
+            assert_eq!(base64_decode(text).as_deref(), Some(bytes), "{text}");
+        }
+    }
+
+    #[test]
+    fn base64_round_trip() {
+        let bytes = (0..=255).collect::<Vec<u8>>();
+        for len in 0..bytes.len() {
+            let text = base64_encode(&bytes[..len]);
+            assert_eq!(text.len(), len.div_ceil(3) * 4);
+            assert_eq!(base64_decode(&text).as_deref(), Some(&bytes[..len]));
+        }
+    }
+
+    #[test]
+    fn base64_invalid() {
+        for text in ["Zm9", "Zm9v!A==", "Z===", "Zg==Zm9v", "Z=g="] {
+            assert_eq!(base64_decode(text), None, "{text}");
+        }
+    }
+}